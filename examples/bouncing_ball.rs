@@ -4,24 +4,25 @@ use chipmunk::space::Space;
 use chipmunk::body::Body;
 use chipmunk::shape::Shape;
 use chipmunk::util::*;
+use chipmunk::vect::Vect;
 
 fn main() {
-    let gravity = (0.0, -100.0);
+    let gravity = Vect::new(0.0, -100.0);
     let floor_friction = 1.0;
     let ball_friction = 0.7;
     let ball_radius = 5.0;
     let ball_mass = 1.0;
-    let ball_pos = (0.0, 15.0);
-    let ball_moment = moment_of_circle(ball_mass, ball_radius, 0.0);
-    let floor_start = (-20.0, 0.0);
-    let floor_end = (20.0, 0.0);
+    let ball_pos = Vect::new(0.0, 15.0);
+    let ball_moment = moment_of_circle(ball_mass, 0.0, ball_radius, Vect::zero());
+    let floor_start = Vect::new(-20.0, 0.0);
+    let floor_end = Vect::new(20.0, 0.0);
     let floor_radius = 0.0;
-    let zero = (0.0, 0.0);
+    let zero = Vect::zero();
     let time_step = 1.0 / 60.0;
 
     // The space contains everything in the simulation.
     let mut space = Space::new();
-    space.set_gravity(gravity.0, gravity.1);
+    space.set_gravity(gravity);
 
     // Set up a floor for our ball to bounce off of.
     let mut floor_body = Body::new_static();
@@ -37,7 +38,7 @@ fn main() {
     let mut ball_body = Body::new(ball_mass, ball_moment);
     let mut ball_shape = Shape::new_circle(&mut ball_body, ball_radius, zero);
 
-    ball_body.set_position(ball_pos.0, ball_pos.1);
+    ball_body.set_position(ball_pos);
     ball_shape.set_friction(ball_friction);
 
     space.add_body(&mut ball_body);
@@ -51,7 +52,7 @@ fn main() {
         let pos = ball_body.position();
         let vel = ball_body.velocity();
         space.step(time_step);
-        y_coords.push(pos.1);
+        y_coords.push(pos.y);
 
         println!("t: {:?}, p: {:?}, v: {:?}", time, pos, vel);
     }
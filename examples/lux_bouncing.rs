@@ -8,6 +8,7 @@ use chipmunk::space::Space;
 use chipmunk::body::Body;
 use chipmunk::shape::Shape;
 use chipmunk::util::*;
+use chipmunk::vect::Vect;
 
 struct MyGame {
     space: Space,
@@ -21,7 +22,7 @@ impl Game for MyGame {
     }
 
     fn render(&mut self, lag: f32, _window: &mut Window, frame: &mut Frame) -> LuxResult<()> {
-        let (x, y) = self.ball_body.position();
+        let Vect { x, y } = self.ball_body.position();
         frame.circle(50.0, y as f32 * 10.0, 10.0).fill();
         println!("{}, {}", x, y);
         Ok(())
@@ -33,22 +34,22 @@ impl Game for MyGame {
 }
 
 fn main() {
-    let gravity = (0.0, -100.0);
+    let gravity = Vect::new(0.0, -100.0);
     let floor_friction = 1.0;
     let ball_friction = 0.7;
     let ball_radius = 5.0;
     let ball_mass = 1.0;
-    let ball_pos = (0.0, 100.0);
-    let ball_moment = moment_of_circle(ball_mass, ball_radius, 0.0);
-    let floor_start = (-20.0, 0.0);
-    let floor_end = (20.0, 0.0);
+    let ball_pos = Vect::new(0.0, 100.0);
+    let ball_moment = moment_of_circle(ball_mass, 0.0, ball_radius, Vect::zero());
+    let floor_start = Vect::new(-20.0, 0.0);
+    let floor_end = Vect::new(20.0, 0.0);
     let floor_radius = 0.0;
-    let zero = (0.0, 0.0);
+    let zero = Vect::zero();
     let time_step = 1.0 / 60.0;
 
     // The space contains everything in the simulation.
     let mut space = Space::new();
-    space.set_gravity(gravity.0, gravity.1);
+    space.set_gravity(gravity);
 
     // Set up a floor for our ball to bounce off of.
     let mut floor_body = Body::new_static();
@@ -64,7 +65,7 @@ fn main() {
     let mut ball_body = Body::new(ball_mass, ball_moment);
     let mut ball_shape = Shape::new_circle(&mut ball_body, ball_radius, zero);
 
-    ball_body.set_position(ball_pos.0, ball_pos.1);
+    ball_body.set_position(ball_pos);
     ball_shape.set_friction(ball_friction);
 
     space.add_body(&mut ball_body);
@@ -0,0 +1,81 @@
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::f64::consts::PI;
+
+/// An angle in radians.
+///
+/// Also used for angular velocities, in radians / second.  Every API that
+/// takes an angle accepts either `Radians` or `Degrees`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Radians(pub f64);
+
+/// An angle in degrees.
+///
+/// Also used for angular velocities, in degrees / second.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Degrees(pub f64);
+
+impl Radians {
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0 * (180.0 / PI))
+    }
+}
+
+impl Degrees {
+    pub fn to_radians(self) -> Radians {
+        Radians(self.0 * (PI / 180.0))
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(angle: Degrees) -> Radians {
+        angle.to_radians()
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(angle: Radians) -> Degrees {
+        angle.to_degrees()
+    }
+}
+
+macro_rules! angle_ops {
+    ($unit:ident) => {
+        impl Add for $unit {
+            type Output = $unit;
+            fn add(self, other: $unit) -> $unit {
+                $unit(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = $unit;
+            fn sub(self, other: $unit) -> $unit {
+                $unit(self.0 - other.0)
+            }
+        }
+
+        impl Neg for $unit {
+            type Output = $unit;
+            fn neg(self) -> $unit {
+                $unit(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $unit {
+            type Output = $unit;
+            fn mul(self, scale: f64) -> $unit {
+                $unit(self.0 * scale)
+            }
+        }
+
+        impl Div<f64> for $unit {
+            type Output = $unit;
+            fn div(self, scale: f64) -> $unit {
+                $unit(self.0 / scale)
+            }
+        }
+    };
+}
+
+angle_ops!(Radians);
+angle_ops!(Degrees);
@@ -1,107 +1,315 @@
 use std::any::Any;
+use std::error::Error;
+use std::fmt;
 use std::rc::Rc;
 use std::cell::UnsafeCell;
 use std::marker::PhantomData;
+use std::mem::transmute;
+use std::ptr;
 
 use super::user_data::UserData;
+use super::body::Body;
+use super::shape::Shape;
+use super::space;
+use super::vect::Vect;
 
 use chip;
 use void::Void;
 
-// TODO: somehow you need to allow the arbiters to return Bodies
-
 pub struct Arbiter<T=Void> {
     raw: Rc<UnsafeCell<ArbiterRaw<T>>>
 }
 
+/// The collision callback that an arbiter was passed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionPhase {
+    Begin,
+    PreSolve,
+    PostSolve,
+    Separate
+}
+
+/// Returned by arbiter queries that are called from the wrong callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseError {
+    /// The callback in which the query is valid.
+    pub expected: CollisionPhase,
+    /// The callback in which the query was made.
+    pub actual: CollisionPhase
+}
+
+impl fmt::Display for PhaseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "arbiter query is only valid in the {:?} callback, not in {:?}",
+               self.expected, self.actual)
+    }
+}
+
+impl Error for PhaseError {}
+
 pub struct ContactPointSet {
     pub count: u32,
-    pub normal: (f64, f64),
+    pub normal: Vect,
     pub points: [ContactPoint; 2]
 }
 
 pub struct ContactPoint {
-    pub a: (f64, f64),
-    pub b: (f64, f64),
+    pub a: Vect,
+    pub b: Vect,
     pub dist: f64
 }
 
-struct ArbiterRaw<T=Void> {
-    cp_arbiter: chip::cpArbiter,
-    user_data: Option<Box<Any>>,
-    _phantom: PhantomData<T>
-}
-
-
-impl <T> ArbiterRaw<T> {
-    fn contact_point_set(&self) -> ContactPointSet {
-        let cps = unsafe { chip::cpArbiterGetContactPointSet(&self.cp_arbiter) };
-
+impl From<chip::cpContactPointSet> for ContactPointSet {
+    fn from(cps: chip::cpContactPointSet) -> ContactPointSet {
         ContactPointSet {
             count: cps.count as u32,
-            normal: (cps.normal.x, cps.normal.y),
+            normal: cps.normal.into(),
             points: [
                 ContactPoint {
-                    a: cps.points[0].pointA.to_tuple(),
-                    b: cps.points[0].pointB.to_tuple(),
+                    a: cps.points[0].pointA.into(),
+                    b: cps.points[0].pointB.into(),
                     dist: cps.points[0].distance
                 },
                 ContactPoint {
-                    a: cps.points[1].pointA.to_tuple(),
-                    b: cps.points[1].pointB.to_tuple(),
+                    a: cps.points[1].pointA.into(),
+                    b: cps.points[1].pointB.into(),
                     dist: cps.points[1].distance
                 }
             ]
 
         }
     }
+}
+
+struct ArbiterRaw<T=Void> {
+    /// Arbiters are owned by the space, so this only points at one.
+    cp_arbiter: *mut chip::cpArbiter,
+    /// The space that is running the callback that received the arbiter.
+    cp_space: *mut chip::cpSpace,
+    phase: CollisionPhase,
+    _phantom: PhantomData<T>
+}
+
+
+impl <T> ArbiterRaw<T> {
+    fn contact_point_set(&self) -> ContactPointSet {
+        unsafe { chip::cpArbiterGetContactPointSet(self.cp_arbiter).into() }
+    }
+
+    fn shapes(&self) -> Option<(Shape<Void>, Shape<Void>)> {
+        unsafe {
+            let mut a = ptr::null_mut();
+            let mut b = ptr::null_mut();
+            chip::cpArbiterGetShapes(self.cp_arbiter, &mut a, &mut b);
+            match (space::find_shape(self.cp_space, a), space::find_shape(self.cp_space, b)) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None
+            }
+        }
+    }
+
+    fn phase(&self) -> CollisionPhase {
+        self.phase
+    }
+
+    fn check_phase(&self, expected: CollisionPhase) -> Result<(), PhaseError> {
+        if self.phase == expected {
+            Ok(())
+        } else {
+            Err(PhaseError { expected: expected, actual: self.phase })
+        }
+    }
+
+    fn total_impulse(&self) -> Result<Vect, PhaseError> {
+        self.check_phase(CollisionPhase::PostSolve)?;
+        unsafe { Ok(chip::cpArbiterTotalImpulse(self.cp_arbiter).into()) }
+    }
+
+    fn total_ke(&self) -> Result<f64, PhaseError> {
+        self.check_phase(CollisionPhase::PostSolve)?;
+        unsafe { Ok(chip::cpArbiterTotalKE(self.cp_arbiter)) }
+    }
 
     fn count(&self) -> u32 {
-        unsafe { chip::cpArbiterGetCount(&self.cp_arbiter) as u32 }
+        unsafe { chip::cpArbiterGetCount(self.cp_arbiter) as u32 }
     }
 
     fn depth(&self, i: u32) -> f64 {
-        unsafe { chip::cpArbiterGetDepth(&self.cp_arbiter, i as i32) }
+        unsafe { chip::cpArbiterGetDepth(self.cp_arbiter, i as i32) }
     }
 
     fn friction(&self) -> f64 {
-        unsafe { chip::cpArbiterGetFriction(&self.cp_arbiter) }
+        unsafe { chip::cpArbiterGetFriction(self.cp_arbiter) }
     }
 
-    fn normal(&self) -> (f64, f64) {
-        unsafe { chip::cpArbiterGetNormal(&self.cp_arbiter).to_tuple() }
+    fn normal(&self) -> Vect {
+        unsafe { chip::cpArbiterGetNormal(self.cp_arbiter).into() }
     }
 
-    fn point_a(&self, i: u32) -> (f64, f64) {
-        unsafe { chip::cpArbiterGetPointA(&self.cp_arbiter, i as i32).to_tuple() }
+    fn point_a(&self, i: u32) -> Vect {
+        unsafe { chip::cpArbiterGetPointA(self.cp_arbiter, i as i32).into() }
     }
 
-    fn point_b(&self, i: u32) -> (f64, f64) {
-        unsafe { chip::cpArbiterGetPointB(&self.cp_arbiter, i as i32).to_tuple() }
+    fn point_b(&self, i: u32) -> Vect {
+        unsafe { chip::cpArbiterGetPointB(self.cp_arbiter, i as i32).into() }
+    }
+
+    fn is_first_contact(&self) -> bool {
+        unsafe { chip::cpArbiterIsFirstContact(self.cp_arbiter) != 0 }
+    }
+
+    fn is_removal(&self) -> bool {
+        unsafe { chip::cpArbiterIsRemoval(self.cp_arbiter) != 0 }
     }
 
     fn restitution(&self) -> f64 {
-        unsafe { chip::cpArbiterGetRestitution(&self.cp_arbiter) }
+        unsafe { chip::cpArbiterGetRestitution(self.cp_arbiter) }
     }
 
-    fn surface_velocity(&self) -> (f64, f64) {
-        unsafe { chip::cpArbiterGetSurfaceVelocity(&self.cp_arbiter).to_tuple() }
+    fn surface_velocity(&self) -> Vect {
+        unsafe { chip::cpArbiterGetSurfaceVelocity(self.cp_arbiter).into() }
     }
 
     fn set_friction(&mut self, friction: f64) {
-        unsafe { chip::cpArbiterSetFriction(&mut self.cp_arbiter, friction) };
+        unsafe { chip::cpArbiterSetFriction(self.cp_arbiter, friction) };
     }
 
     fn set_restitution(&mut self, restitution: f64) {
-        unsafe { chip::cpArbiterSetRestitution(&mut self.cp_arbiter, restitution) };
+        unsafe { chip::cpArbiterSetRestitution(self.cp_arbiter, restitution) };
+    }
+
+    fn set_surface_velocity(&mut self, velocity: Vect) {
+        unsafe { chip::cpArbiterSetSurfaceVelocity(self.cp_arbiter, velocity.into()) };
+    }
+
+    fn ignore(&mut self) -> bool {
+        unsafe { chip::cpArbiterIgnore(self.cp_arbiter) != 0 }
+    }
+
+    fn call_wildcard_begin_a(&mut self) -> bool {
+        unsafe { chip::cpArbiterCallWildcardBeginA(self.cp_arbiter, self.cp_space) != 0 }
+    }
+
+    fn call_wildcard_begin_b(&mut self) -> bool {
+        unsafe { chip::cpArbiterCallWildcardBeginB(self.cp_arbiter, self.cp_space) != 0 }
+    }
+
+    fn call_wildcard_begin(&mut self) -> bool {
+        let a = self.call_wildcard_begin_a();
+        let b = self.call_wildcard_begin_b();
+        a && b
+    }
+
+    fn call_wildcard_pre_solve_a(&mut self) -> bool {
+        unsafe { chip::cpArbiterCallWildcardPreSolveA(self.cp_arbiter, self.cp_space) != 0 }
+    }
+
+    fn call_wildcard_pre_solve_b(&mut self) -> bool {
+        unsafe { chip::cpArbiterCallWildcardPreSolveB(self.cp_arbiter, self.cp_space) != 0 }
+    }
+
+    fn call_wildcard_pre_solve(&mut self) -> bool {
+        let a = self.call_wildcard_pre_solve_a();
+        let b = self.call_wildcard_pre_solve_b();
+        a && b
+    }
+
+    fn call_wildcard_post_solve_a(&mut self) {
+        unsafe { chip::cpArbiterCallWildcardPostSolveA(self.cp_arbiter, self.cp_space) };
     }
 
-    fn set_surface_velocity(&mut self, vx: f64, vy: f64) {
-        unsafe { chip::cpArbiterSetSurfaceVelocity(&mut self.cp_arbiter, chip::cpv(vx, vy)) };
+    fn call_wildcard_post_solve_b(&mut self) {
+        unsafe { chip::cpArbiterCallWildcardPostSolveB(self.cp_arbiter, self.cp_space) };
+    }
+
+    fn call_wildcard_post_solve(&mut self) {
+        self.call_wildcard_post_solve_a();
+        self.call_wildcard_post_solve_b();
+    }
+
+    fn call_wildcard_separate_a(&mut self) {
+        unsafe { chip::cpArbiterCallWildcardSeparateA(self.cp_arbiter, self.cp_space) };
+    }
+
+    fn call_wildcard_separate_b(&mut self) {
+        unsafe { chip::cpArbiterCallWildcardSeparateB(self.cp_arbiter, self.cp_space) };
+    }
+
+    fn call_wildcard_separate(&mut self) {
+        self.call_wildcard_separate_a();
+        self.call_wildcard_separate_b();
+    }
+}
+
+impl Arbiter<Void> {
+    // TODO: hide doc
+    /// Wraps an arbiter that is owned by a space.
+    ///
+    /// The arbiter is only valid for the duration of the callback that it
+    /// was passed to.
+    pub unsafe fn from_cp_arbiter(cp_arbiter: *mut chip::cpArbiter,
+                                  cp_space: *mut chip::cpSpace,
+                                  phase: CollisionPhase) -> Arbiter<Void> {
+        Arbiter {
+            raw: Rc::new(UnsafeCell::new(ArbiterRaw {
+                cp_arbiter: cp_arbiter,
+                cp_space: cp_space,
+                phase: phase,
+                _phantom: PhantomData
+            }))
+        }
     }
 }
 
 impl <T> Arbiter<T> {
+    // TODO: hide doc
+    pub unsafe fn get_cp_arbiter(&self) -> *mut chip::cpArbiter {
+        (*self.raw.get()).cp_arbiter
+    }
+
+    /// Returns the two shapes that are colliding.
+    ///
+    /// The shapes are in the same order as the collision types of the
+    /// handler that received this arbiter.  `S` is the type of user data
+    /// attached to the shapes.
+    ///
+    /// Returns `None` if either shape wasn't added through `Space`.
+    pub fn shapes<S>(&self) -> Option<(Shape<S>, Shape<S>)> {
+        unsafe {
+            (*self.raw.get()).shapes().map(|shapes| transmute(shapes))
+        }
+    }
+
+    /// Returns the bodies of the two shapes that are colliding.
+    ///
+    /// The bodies are in the same order as the shapes returned by
+    /// `shapes()`.  `B` is the type of user data attached to the bodies.
+    pub fn bodies<B>(&self) -> Option<(Body<B>, Body<B>)> {
+        self.shapes::<Void>().map(|(a, b)| unsafe {
+            transmute((a.body(), b.body()))
+        })
+    }
+
+    forward!(phase(&self) -> CollisionPhase,
+    /// Returns the collision callback that this arbiter was passed to.
+    );
+
+    forward!(total_impulse(&self) -> Result<Vect, PhaseError>,
+    /// Returns the impulse that was applied this step to resolve the
+    /// collision.
+    ///
+    /// Only valid in a post-solve callback, and returns an error anywhere
+    /// else.
+    );
+
+    forward!(total_ke(&self) -> Result<f64, PhaseError>,
+    /// Returns the amount of energy lost in the collision this step,
+    /// including static friction.
+    ///
+    /// Only valid in a post-solve callback, and returns an error anywhere
+    /// else.
+    );
+
     forward!(contact_point_set(&self) -> ContactPointSet,
     /// Returns the set of contact points.
     );
@@ -118,15 +326,28 @@ impl <T> Arbiter<T> {
     /// Returns the friction of the contact.
     );
 
-    forward!(normal(&self) -> (f64, f64),
+    forward!(normal(&self) -> Vect,
     /// Returns the normal vector of the collision.
+    ///
+    /// The normal points from the first shape returned by `shapes()`
+    /// towards the second, so its orientation follows the order of the
+    /// collision types of the handler that received this arbiter.
+    );
+
+    forward!(is_first_contact(&self) -> bool,
+    /// Returns true if this is the first step in which the shapes touch.
     );
 
-    forward!(point_a(&self, i: u32) -> (f64, f64),
+    forward!(is_removal(&self) -> bool,
+    /// Returns true if the separate callback was called because one of the
+    /// shapes was removed from the space.
+    );
+
+    forward!(point_a(&self, i: u32) -> Vect,
     /// Returns a point on object `a` in the colision for a point of intersection.
     );
 
-    forward!(point_b(&self, i: u32) -> (f64, f64),
+    forward!(point_b(&self, i: u32) -> Vect,
     /// Returns a point on object `b` in the colision for a point of intersection.
     );
 
@@ -134,13 +355,16 @@ impl <T> Arbiter<T> {
     /// Returns the restitution for this collision.
     );
 
-    forward!(surface_velocity(&self) -> (f64, f64),
+    forward!(surface_velocity(&self) -> Vect,
     /// Returns the surface velocity of this collision.
     );
 
-    forward!(set_surface_velocity(&mut self, vx: f64, vy: f64) -> (),
     /// Sets the surface velocity for this collision.
-    );
+    pub fn set_surface_velocity<P: Into<Vect>>(&mut self, velocity: P) {
+        unsafe {
+            (*self.raw.get()).set_surface_velocity(velocity.into())
+        }
+    }
 
     forward!(set_friction(&mut self, friction: f64) -> (),
     /// Sets the friction for this collision.
@@ -149,15 +373,98 @@ impl <T> Arbiter<T> {
     forward!(set_restitution(&mut self, restitution: f64) -> (),
     /// Sets the restitutionfor this collision.
     );
+
+    forward!(ignore(&mut self) -> bool,
+    /// Makes chipmunk ignore this collision until the shapes separate.
+    ///
+    /// Always returns false, so that a begin or pre-solve closure can
+    /// reject the contact with `return arbiter.ignore();`.
+    );
+
+    forward!(call_wildcard_begin_a(&mut self) -> bool,
+    /// Calls the begin closure of the wildcard handler for the collision
+    /// type of shape `a`.
+    ///
+    /// Returns false if the wildcard handler rejected the collision.
+    );
+
+    forward!(call_wildcard_begin_b(&mut self) -> bool,
+    /// Calls the begin closure of the wildcard handler for the collision
+    /// type of shape `b`.
+    ///
+    /// Returns false if the wildcard handler rejected the collision.
+    );
+
+    forward!(call_wildcard_begin(&mut self) -> bool,
+    /// Calls the begin closures of the wildcard handlers for both shapes.
+    ///
+    /// Both closures are always called.  Returns false if either of them
+    /// rejected the collision.
+    );
+
+    forward!(call_wildcard_pre_solve_a(&mut self) -> bool,
+    /// Calls the pre-solve closure of the wildcard handler for the
+    /// collision type of shape `a`.
+    );
+
+    forward!(call_wildcard_pre_solve_b(&mut self) -> bool,
+    /// Calls the pre-solve closure of the wildcard handler for the
+    /// collision type of shape `b`.
+    );
+
+    forward!(call_wildcard_pre_solve(&mut self) -> bool,
+    /// Calls the pre-solve closures of the wildcard handlers for both
+    /// shapes.
+    ///
+    /// Both closures are always called.  Returns false if either of them
+    /// rejected the collision.
+    );
+
+    forward!(call_wildcard_post_solve_a(&mut self) -> (),
+    /// Calls the post-solve closure of the wildcard handler for the
+    /// collision type of shape `a`.
+    );
+
+    forward!(call_wildcard_post_solve_b(&mut self) -> (),
+    /// Calls the post-solve closure of the wildcard handler for the
+    /// collision type of shape `b`.
+    );
+
+    forward!(call_wildcard_post_solve(&mut self) -> (),
+    /// Calls the post-solve closures of the wildcard handlers for both
+    /// shapes.
+    );
+
+    forward!(call_wildcard_separate_a(&mut self) -> (),
+    /// Calls the separate closure of the wildcard handler for the
+    /// collision type of shape `a`.
+    );
+
+    forward!(call_wildcard_separate_b(&mut self) -> (),
+    /// Calls the separate closure of the wildcard handler for the
+    /// collision type of shape `b`.
+    );
+
+    forward!(call_wildcard_separate(&mut self) -> (),
+    /// Calls the separate closures of the wildcard handlers for both
+    /// shapes.
+    );
 }
 
+/// The user data of an arbiter is kept by its space, so that every callback
+/// of the same collision sees it.  It is dropped once the collision ends.
 impl <T: Any> UserData<T> for Arbiter<T> {
     fn get_userdata_box(&self) -> &Option<Box<Any>> {
-        unsafe { &(*self.raw.get()).user_data }
+        unsafe {
+            let raw = &*self.raw.get();
+            space::arbiter_user_data(raw.cp_space, raw.cp_arbiter)
+        }
     }
 
     fn get_userdata_mut_box(&mut self) -> &mut Option<Box<Any>> {
-        unsafe { &mut (*self.raw.get()).user_data }
-
+        unsafe {
+            let raw = &*self.raw.get();
+            space::arbiter_user_data(raw.cp_space, raw.cp_arbiter)
+        }
     }
 }
@@ -0,0 +1,141 @@
+use super::vect::Vect;
+
+use chip;
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BB {
+    /// The left edge.
+    pub l: f64,
+    /// The bottom edge.
+    pub b: f64,
+    /// The right edge.
+    pub r: f64,
+    /// The top edge.
+    pub t: f64
+}
+
+impl BB {
+    pub fn new(l: f64, b: f64, r: f64, t: f64) -> BB {
+        BB { l: l, b: b, r: r, t: t }
+    }
+
+    /// Returns the box with the corners `min` (bottom left) and `max`
+    /// (top right).
+    pub fn from_corners<P: Into<Vect>, Q: Into<Vect>>(min: P, max: Q) -> BB {
+        let (min, max): (Vect, Vect) = (min.into(), max.into());
+        BB::new(min.x, min.y, max.x, max.y)
+    }
+
+    /// Returns a box centered on `center` that extends `half_width` to
+    /// the left and right and `half_height` up and down.
+    pub fn for_extents<P: Into<Vect>>(center: P, half_width: f64, half_height: f64) -> BB {
+        let center: Vect = center.into();
+        BB::new(center.x - half_width, center.y - half_height,
+                center.x + half_width, center.y + half_height)
+    }
+
+    /// Returns the box that encloses a circle.
+    pub fn for_circle<P: Into<Vect>>(center: P, radius: f64) -> BB {
+        BB::for_extents(center, radius, radius)
+    }
+
+    /// Returns true if the two boxes overlap.
+    pub fn intersects(&self, other: &BB) -> bool {
+        self.l <= other.r && other.l <= self.r && self.b <= other.t && other.b <= self.t
+    }
+
+    /// Returns true if `other` lies completely inside this box.
+    pub fn contains(&self, other: &BB) -> bool {
+        self.l <= other.l && self.r >= other.r && self.b <= other.b && self.t >= other.t
+    }
+
+    /// Returns true if `point` lies inside this box.
+    pub fn contains_vect<P: Into<Vect>>(&self, point: P) -> bool {
+        let point: Vect = point.into();
+        self.l <= point.x && self.r >= point.x && self.b <= point.y && self.t >= point.y
+    }
+
+    /// Returns the smallest box that encloses both boxes.
+    pub fn merge(&self, other: &BB) -> BB {
+        BB::new(self.l.min(other.l), self.b.min(other.b),
+                self.r.max(other.r), self.t.max(other.t))
+    }
+
+    /// Returns the smallest box that encloses this box and `point`.
+    pub fn expand<P: Into<Vect>>(&self, point: P) -> BB {
+        let point: Vect = point.into();
+        BB::new(self.l.min(point.x), self.b.min(point.y),
+                self.r.max(point.x), self.t.max(point.y))
+    }
+
+    /// Returns this box moved by `offset`.
+    pub fn offset<P: Into<Vect>>(&self, offset: P) -> BB {
+        let offset: Vect = offset.into();
+        BB::new(self.l + offset.x, self.b + offset.y,
+                self.r + offset.x, self.t + offset.y)
+    }
+
+    /// Returns the bottom left corner.
+    pub fn min(&self) -> Vect {
+        Vect::new(self.l, self.b)
+    }
+
+    /// Returns the top right corner.
+    pub fn max(&self) -> Vect {
+        Vect::new(self.r, self.t)
+    }
+
+    pub fn center(&self) -> Vect {
+        self.min().lerp(self.max(), 0.5)
+    }
+
+    pub fn width(&self) -> f64 {
+        self.r - self.l
+    }
+
+    pub fn height(&self) -> f64 {
+        self.t - self.b
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Returns the area of the box that `merge()` would return.
+    pub fn merged_area(&self, other: &BB) -> f64 {
+        self.merge(other).area()
+    }
+
+    /// Returns the point inside this box that is closest to `point`.
+    pub fn clamp<P: Into<Vect>>(&self, point: P) -> Vect {
+        let point: Vect = point.into();
+        Vect::new(point.x.max(self.l).min(self.r), point.y.max(self.b).min(self.t))
+    }
+
+    /// Wraps `point` around this box, as if opposite edges were joined.
+    pub fn wrap<P: Into<Vect>>(&self, point: P) -> Vect {
+        let point: Vect = point.into();
+        let dx = (self.r - self.l).abs();
+        let modx = (point.x - self.l) % dx;
+        let x = if modx > 0.0 { modx } else { modx + dx };
+
+        let dy = (self.t - self.b).abs();
+        let mody = (point.y - self.b) % dy;
+        let y = if mody > 0.0 { mody } else { mody + dy };
+
+        Vect::new(x + self.l, y + self.b)
+    }
+}
+
+impl From<chip::cpBB> for BB {
+    fn from(bb: chip::cpBB) -> BB {
+        BB::new(bb.l, bb.b, bb.r, bb.t)
+    }
+}
+
+impl From<BB> for chip::cpBB {
+    fn from(bb: BB) -> chip::cpBB {
+        chip::cpBB { l: bb.l, b: bb.b, r: bb.r, t: bb.t }
+    }
+}
@@ -5,6 +5,9 @@ use std::mem;
 use std::marker::PhantomData;
 
 use super::user_data::UserData;
+use super::vect::Vect;
+use super::transform::Transform;
+use super::angle::Radians;
 
 use chip;
 
@@ -13,6 +16,8 @@ use void::Void;
 struct BodyRaw<T=Void> {
     cp_body: chip::cpBody,
     user_data: Option<Box<Any>>,
+    /// A body type change that waits for the space to finish its step.
+    pending_body_type: Option<BodyType>,
     _phantom: PhantomData<T>
 }
 
@@ -20,13 +25,64 @@ pub struct Body<T=Void> {
     raw: Rc<UnsafeCell<BodyRaw<T>>>
 }
 
+/// How a body is simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyType {
+    /// Moved by forces, gravity and collisions.
+    Dynamic,
+    /// Only moved by its velocity, and pushes dynamic bodies out of the way
+    /// with unlimited force.
+    Kinematic,
+    /// Never moves.  Shapes attached to a static body are cheaper to
+    /// collide against.
+    Static
+}
+
+impl From<chip::cpBodyType> for BodyType {
+    fn from(body_type: chip::cpBodyType) -> BodyType {
+        match body_type {
+            chip::CP_BODY_TYPE_DYNAMIC => BodyType::Dynamic,
+            chip::CP_BODY_TYPE_KINEMATIC => BodyType::Kinematic,
+            _ => BodyType::Static
+        }
+    }
+}
+
+impl From<BodyType> for chip::cpBodyType {
+    fn from(body_type: BodyType) -> chip::cpBodyType {
+        match body_type {
+            BodyType::Dynamic => chip::CP_BODY_TYPE_DYNAMIC,
+            BodyType::Kinematic => chip::CP_BODY_TYPE_KINEMATIC,
+            BodyType::Static => chip::CP_BODY_TYPE_STATIC
+        }
+    }
+}
+
 impl Body<Void> {
+    /// Creates a dynamic body with a fixed mass and moment of inertia.
+    ///
+    /// Once any attached shape is given a mass or density, the masses of
+    /// the shapes replace the mass, moment and center of gravity set here,
+    /// see `new_dynamic()`.
     pub fn new(mass: f64, moment: f64) -> Body<Void> {
         Body {
             raw: Rc::new(UnsafeCell::new(BodyRaw::new(mass, moment)))
         }
     }
 
+    /// Creates a dynamic body that takes its mass, moment of inertia and
+    /// center of gravity from its shapes.
+    ///
+    /// Give the shapes a mass or density with `Shape::set_mass()` or
+    /// `Shape::set_density()`.  Chipmunk recomputes the mass properties of
+    /// the body whenever one of its shapes is added to or removed from a
+    /// space, or has its mass or density changed, and keeps the position of
+    /// the body in place while doing so.  Until then the body has no mass,
+    /// so it should not be stepped without any shapes.
+    pub fn new_dynamic() -> Body<Void> {
+        Body::new(0.0, 0.0)
+    }
+
     pub fn new_kinematic() -> Body<Void> {
         Body {
             raw: Rc::new(UnsafeCell::new(BodyRaw::new_kinematic()))
@@ -53,28 +109,37 @@ impl <T> Body<T> {
         &mut (*self.raw.get()).cp_body
     }
 
-    forward!(angle_rad(&self) -> f64,
-    /// Returns the rotation angle of the body in radians.
+    forward!(body_type(&self) -> BodyType,
+    /// Returns how the body is simulated.
     );
 
-    forward!(angle_deg(&self) -> f64,
-    /// Returns the rotation angle fo the body in degrees.
+    forward!(set_body_type(&mut self, body_type: BodyType) -> (),
+    /// Changes how the body is simulated.
+    ///
+    /// This works while the body is in a space.  When it is called from a
+    /// callback during `Space::step()`, the last change is made once the
+    /// step is over, but `body_type()` returns it right away.
+    ///
+    /// A body that becomes dynamic takes its mass and moment of inertia
+    /// from its shapes, see `new_dynamic()`; set them afterwards if its
+    /// shapes have no mass or density.  A body that becomes kinematic or
+    /// static loses its velocity.
     );
 
-    forward!(angular_velocity_rad(&self) -> f64,
-    /// Returns the angular velocity in radians / second.
+    forward!(angle(&self) -> Radians,
+    /// Returns the rotation angle of the body.
     );
 
-    forward!(angular_velocity_deg(&self) -> f64,
-    /// Returns the angular velocity in degrees / second.
+    forward!(angular_velocity(&self) -> Radians,
+    /// Returns the angular velocity (per second).
     );
 
-    forward!(center_of_gravity(&self) -> (f64, f64),
+    forward!(center_of_gravity(&self) -> Vect,
     /// Returns the location of the center of gravity in
     /// local coordinates.
     );
 
-    forward!(force(&self) -> (f64, f64),
+    forward!(force(&self) -> Vect,
     /// Returns the force acting on the body.
     );
 
@@ -86,7 +151,7 @@ impl <T> Body<T> {
     /// Returns the moment of inertia of the body.
     );
 
-    forward!(position(&self) -> (f64, f64),
+    forward!(position(&self) -> Vect,
     /// Returns the position of the body in world space.
     );
 
@@ -94,39 +159,49 @@ impl <T> Body<T> {
     /// Returns the torque acting on the body.
     );
 
-    forward!(velocity(&self) -> (f64, f64),
-    /// Returns the velocity of the body.
+    forward!(transform(&self) -> Transform,
+    /// Returns the transformation from local to world coordinates.
+    ///
+    /// Use `Transform::to_matrix()` to hand it to a renderer.
     );
 
-
-    forward!(set_angle_rad(&mut self, angle: f64) -> (),
-    /// Sets the angle of the object in space (in radians).
+    forward!(velocity(&self) -> Vect,
+    /// Returns the velocity of the body.
     );
 
-    forward!(set_angle_deg(&mut self, angle: f64) -> (),
-    /// Sets the angle of the object in space (in degrees).
-    );
 
-    forward!(set_angular_velocity_rad(&mut self, ang_vel: f64) -> (),
-    /// Sets the angular velocity in radians / second.
-    ) ;
+    /// Sets the angle of the object in space.
+    pub fn set_angle<A: Into<Radians>>(&mut self, angle: A) {
+        unsafe {
+            (*self.raw.get()).set_angle(angle.into())
+        }
+    }
 
-    forward!(set_angular_velocity_deg(&mut self, ang_vel: f64) -> (),
-    /// Sets the angular velocity in degrees / second.
-    ) ;
+    /// Sets the angular velocity (per second).
+    pub fn set_angular_velocity<A: Into<Radians>>(&mut self, ang_vel: A) {
+        unsafe {
+            (*self.raw.get()).set_angular_velocity(ang_vel.into())
+        }
+    }
 
-    forward!(set_center_of_gravity(&mut self, x: f64, y: f64) -> (),
     /// Sets the position of the center of gravity on this body.
     ///
     /// The center of gravity is in local coordinates.
-    );
+    pub fn set_center_of_gravity<P: Into<Vect>>(&mut self, cog: P) {
+        unsafe {
+            (*self.raw.get()).set_center_of_gravity(cog.into())
+        }
+    }
 
-    forward!(set_force(&mut self, x: f64, y: f64) -> (),
     /// Sets the force applied to the body.
     ///
     /// The force is not reset during each physics step.  If you want
     /// to reset the force, you must do that manually.
-    );
+    pub fn set_force<P: Into<Vect>>(&mut self, force: P) {
+        unsafe {
+            (*self.raw.get()).set_force(force.into())
+        }
+    }
 
     forward!(set_mass(&mut self, mass: f64) -> (),
     /// Sets the mass of the body.
@@ -138,17 +213,23 @@ impl <T> Body<T> {
     /// The moment of inertia is the rotational mass of the body.
     );
 
-    forward!(set_position(&mut self, x: f64, y: f64) -> (),
     /// Sets the position of the body in world coordinates.
-    );
+    pub fn set_position<P: Into<Vect>>(&mut self, position: P) {
+        unsafe {
+            (*self.raw.get()).set_position(position.into())
+        }
+    }
 
     forward!(set_torque(&mut self, torque: f64) -> (),
     /// Sets the torque applied to the body.
     );
 
-    forward!(set_velocity(&mut self, x: f64, y: f64) -> (),
     /// Directly sets the velocity of the body.
-    );
+    pub fn set_velocity<P: Into<Vect>>(&mut self, velocity: P) {
+        unsafe {
+            (*self.raw.get()).set_velocity(velocity.into())
+        }
+    }
 }
 
 impl <T> BodyRaw<T> {
@@ -157,6 +238,7 @@ impl <T> BodyRaw<T> {
             let mut ret = BodyRaw {
                 cp_body: mem::zeroed(),
                 user_data: None,
+                pending_body_type: None,
                 _phantom: PhantomData
             };
             chip::cpBodyInit(&mut ret.cp_body, mass, moment);
@@ -166,53 +248,63 @@ impl <T> BodyRaw<T> {
 
     fn new_kinematic() -> BodyRaw<T> {
         let mut res = BodyRaw::new(0.0, 0.0);
-        unsafe {
-            chip::cpBodySetType(&mut res.cp_body, chip::CP_BODY_TYPE_KINEMATIC);
-        }
+        res.set_body_type(BodyType::Kinematic);
         res
     }
 
     fn new_static() -> BodyRaw<T> {
         let mut res = BodyRaw::new(0.0, 0.0);
-        unsafe {
-            chip::cpBodySetType(&mut res.cp_body, chip::CP_BODY_TYPE_STATIC);
-        }
+        res.set_body_type(BodyType::Static);
         res
     }
 
-    fn angle_rad(&self) -> f64 {
-        unsafe {
-            chip::cpBodyGetAngle(&self.cp_body)
+    fn body_type(&self) -> BodyType {
+        match self.pending_body_type {
+            Some(body_type) => body_type,
+            None => unsafe { chip::cpBodyGetType(&self.cp_body).into() }
         }
     }
 
-    fn angle_deg(&self) -> f64 {
-        use std::f64::consts::PI;
-        self.angle_rad() * (PI / 180.0)
+    fn set_body_type(&mut self, body_type: BodyType) {
+        unsafe {
+            let space = chip::cpBodyGetSpace(&self.cp_body);
+            if !space.is_null() && chip::cpSpaceIsLocked(space) != 0 {
+                // Chipmunk can't move a body between its lists while the
+                // space is stepping, so wait for the step to end.  Chipmunk
+                // only keeps the first callback per key, which then applies
+                // the latest pending type.
+                self.pending_body_type = Some(body_type);
+                let key = &mut self.cp_body as *mut chip::cpBody as chip::cpDataPointer;
+                let data = self as *mut Self as chip::cpDataPointer;
+                chip::cpSpaceAddPostStepCallback(space, Some(set_body_type_post_step::<T>), key, data);
+            } else {
+                self.pending_body_type = None;
+                chip::cpBodySetType(&mut self.cp_body, body_type.into());
+            }
+        }
     }
 
-    fn angular_velocity_rad(&self) -> f64 {
+    fn angle(&self) -> Radians {
         unsafe {
-            chip::cpBodyGetAngularVelocity(&self.cp_body)
+            Radians(chip::cpBodyGetAngle(&self.cp_body))
         }
     }
 
-    fn angular_velocity_deg(&self) -> f64 {
-        use std::f64::consts::PI;
-        self.angular_velocity_rad() * (PI / 180.0)
+    fn angular_velocity(&self) -> Radians {
+        unsafe {
+            Radians(chip::cpBodyGetAngularVelocity(&self.cp_body))
+        }
     }
 
-    fn center_of_gravity(&self) -> (f64, f64) {
+    fn center_of_gravity(&self) -> Vect {
         unsafe {
-            let v = chip::cpBodyGetCenterOfGravity(&self.cp_body);
-            (v.x, v.y)
+            chip::cpBodyGetCenterOfGravity(&self.cp_body).into()
         }
     }
 
-    fn force(&self) -> (f64, f64) {
+    fn force(&self) -> Vect {
         unsafe {
-            let v = chip::cpBodyGetForce(&self.cp_body);
-            (v.x, v.y)
+            chip::cpBodyGetForce(&self.cp_body).into()
         }
     }
 
@@ -228,10 +320,9 @@ impl <T> BodyRaw<T> {
         }
     }
 
-    fn position(&self) -> (f64, f64) {
+    fn position(&self) -> Vect {
         unsafe {
-            let v = chip::cpBodyGetPosition(&self.cp_body);
-            (v.x, v.y)
+            chip::cpBodyGetPosition(&self.cp_body).into()
         }
     }
 
@@ -241,48 +332,37 @@ impl <T> BodyRaw<T> {
         }
     }
 
-    fn velocity(&self) -> (f64, f64) {
-        unsafe {
-            let v = chip::cpBodyGetVelocity(&self.cp_body);
-            (v.x, v.y)
-        }
+    fn transform(&self) -> Transform {
+        Transform::rigid(self.position(), self.angle())
     }
 
-    fn set_angle_rad(&mut self, angle: f64) {
+    fn velocity(&self) -> Vect {
         unsafe {
-            chip::cpBodySetAngle(&mut self.cp_body, angle);
+            chip::cpBodyGetVelocity(&self.cp_body).into()
         }
     }
 
-    fn set_angle_deg(&mut self, angle: f64) {
-        use std::f64::consts::PI;
-        self.set_angle_rad(angle * (180.0 / PI));
-    }
-
-    fn set_angular_velocity_rad(&mut self, ang_vel: f64) {
+    fn set_angle(&mut self, angle: Radians) {
         unsafe {
-            chip::cpBodySetAngularVelocity(&mut self.cp_body,
-                                           ang_vel)
+            chip::cpBodySetAngle(&mut self.cp_body, angle.0);
         }
     }
 
-    fn set_angular_velocity_deg(&mut self, ang_vel: f64) {
-        use std::f64::consts::PI;
+    fn set_angular_velocity(&mut self, ang_vel: Radians) {
         unsafe {
-            chip::cpBodySetAngularVelocity(&mut self.cp_body,
-                                           ang_vel * (180.0 / PI))
+            chip::cpBodySetAngularVelocity(&mut self.cp_body, ang_vel.0)
         }
     }
 
-    fn set_center_of_gravity(&mut self, x: f64, y: f64) {
+    fn set_center_of_gravity(&mut self, cog: Vect) {
         unsafe {
-            chip::cpBodySetCenterOfGravity(&mut self.cp_body, chip::cpv(x, y));
+            chip::cpBodySetCenterOfGravity(&mut self.cp_body, cog.into());
         }
     }
 
-    fn set_force(&mut self, x: f64, y: f64) {
+    fn set_force(&mut self, force: Vect) {
         unsafe {
-            chip::cpBodySetForce(&mut self.cp_body, chip::cpv(x, y));
+            chip::cpBodySetForce(&mut self.cp_body, force.into());
         }
     }
 
@@ -298,9 +378,9 @@ impl <T> BodyRaw<T> {
         }
     }
 
-    fn set_position(&mut self, x: f64, y: f64) {
+    fn set_position(&mut self, position: Vect) {
         unsafe {
-            chip::cpBodySetPosition(&mut self.cp_body, chip::cpv(x, y));
+            chip::cpBodySetPosition(&mut self.cp_body, position.into());
         }
     }
 
@@ -310,16 +390,26 @@ impl <T> BodyRaw<T> {
         }
     }
 
-    fn set_velocity(&mut self, vx: f64, vy: f64) {
+    fn set_velocity(&mut self, velocity: Vect) {
         unsafe {
-            chip::cpBodySetVelocity(&mut self.cp_body, chip::cpv(vx, vy));
+            chip::cpBodySetVelocity(&mut self.cp_body, velocity.into());
         }
     }
 
-    // fn set_type
     // fn setPositionUpdateFunc
 }
 
+/// Applies a body type change that was made while the space was stepping.
+extern "C" fn set_body_type_post_step<T>(_space: *mut chip::cpSpace, _key: chip::cpDataPointer,
+                                         data: chip::cpDataPointer) {
+    unsafe {
+        let raw = data as *mut BodyRaw<T>;
+        if let Some(body_type) = (*raw).pending_body_type.take() {
+            chip::cpBodySetType(&mut (*raw).cp_body, body_type.into());
+        }
+    }
+}
+
 impl <T: 'static + Any> UserData<T> for BodyRaw<T> {
     fn get_userdata_box(&self) -> &Option<Box<Any>> {
         &self.user_data
@@ -0,0 +1,328 @@
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::UnsafeCell;
+
+use super::arbiter::{Arbiter, CollisionPhase, ContactPointSet};
+use super::shape::Shape;
+use super::space;
+use super::vect::Vect;
+
+use chip;
+
+use void::Void;
+
+/// Identifies a group of shapes for the purpose of picking the
+/// collision handlers that are called when they touch.
+pub type CollisionType = chip::cpCollisionType;
+
+/// A closure called when a collision starts or before it is solved.
+///
+/// Returning `false` makes chipmunk ignore the collision.
+pub type FilterFunc<T> = Box<FnMut(&mut Arbiter, Option<&mut T>) -> bool>;
+
+/// A closure called after a collision is solved or once it ends.
+pub type NotifyFunc<T> = Box<FnMut(&mut Arbiter, Option<&mut T>)>;
+
+/// A collision recorded by the space during a step.
+///
+/// See `Space::record_collision_events()`.
+pub struct CollisionEvent {
+    /// The callback that recorded the event: `Begin`, `PostSolve` or
+    /// `Separate`.
+    pub phase: CollisionPhase,
+    /// The colliding shapes, in the order of the handler's collision types.
+    pub shapes: (Shape, Shape),
+    /// The normal of the collision, pointing from the first shape to the
+    /// second.
+    pub normal: Vect,
+    pub contact_point_set: ContactPointSet,
+    /// The impulse applied to resolve the collision.  Only present for
+    /// `PostSolve` events.
+    pub total_impulse: Option<Vect>
+}
+
+/// The set of closures that a space calls when shapes of two
+/// collision types touch.
+///
+/// Every closure receives the arbiter of the collision and the user data
+/// of the space, if it has been set.  Handlers are created with
+/// `Space::add_collision_handler()`, `Space::add_wildcard_handler()` or
+/// `Space::default_collision_handler()` and live as long as the space
+/// does.
+pub struct CollisionHandler<T=Void> {
+    raw: Rc<UnsafeCell<CollisionHandlerRaw<T>>>
+}
+
+struct CollisionHandlerRaw<T=Void> {
+    /// The handler is owned by the space, so this only points at one.
+    cp_handler: *mut chip::cpCollisionHandler,
+    begin: Option<FilterFunc<T>>,
+    pre_solve: Option<FilterFunc<T>>,
+    post_solve: Option<NotifyFunc<T>>,
+    separate: Option<NotifyFunc<T>>,
+    record_events: bool,
+    track_sensors: bool,
+    /// The callbacks that chipmunk installed, called in place of missing
+    /// closures.  These call the wildcard handlers for pair handlers.
+    default_begin: chip::cpCollisionBeginFunc,
+    default_pre_solve: chip::cpCollisionPreSolveFunc,
+    default_post_solve: chip::cpCollisionPostSolveFunc,
+    default_separate: chip::cpCollisionSeparateFunc,
+}
+
+impl <T: 'static + Any> CollisionHandler<T> {
+    // TODO: hide doc
+    /// Wraps a handler that is owned by a space.
+    pub unsafe fn from_cp_handler(cp_handler: *mut chip::cpCollisionHandler) -> CollisionHandler<T> {
+        let handler = CollisionHandler {
+            raw: Rc::new(UnsafeCell::new(CollisionHandlerRaw {
+                cp_handler: cp_handler,
+                begin: None,
+                pre_solve: None,
+                post_solve: None,
+                separate: None,
+                record_events: false,
+                track_sensors: false,
+                default_begin: (*cp_handler).beginFunc,
+                default_pre_solve: (*cp_handler).preSolveFunc,
+                default_post_solve: (*cp_handler).postSolveFunc,
+                default_separate: (*cp_handler).separateFunc,
+            }))
+        };
+        (*cp_handler).userData = handler.raw.get() as chip::cpDataPointer;
+        // The separate callback always runs so that the user data of the
+        // arbiter can be dropped.
+        (*cp_handler).separateFunc = Some(separate_trampoline::<T>);
+        handler
+    }
+
+    // TODO: hide doc
+    pub unsafe fn get_cp_handler(&self) -> *mut chip::cpCollisionHandler {
+        (*self.raw.get()).cp_handler
+    }
+
+    // TODO: hide doc
+    pub unsafe fn duplicate(&self) -> CollisionHandler<T> {
+        CollisionHandler { raw: self.raw.clone() }
+    }
+
+    /// Sets the closure that is called when two shapes first touch.
+    ///
+    /// Returning `false` makes chipmunk ignore the collision until the
+    /// shapes separate again.
+    pub fn set_begin_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Arbiter, Option<&mut T>) -> bool {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.begin = Some(Box::new(func));
+            (*raw.cp_handler).beginFunc = Some(begin_trampoline::<T>);
+        }
+    }
+
+    /// Sets the closure that is called every step while two shapes touch,
+    /// before the collision is solved.
+    ///
+    /// Returning `false` makes chipmunk ignore the collision for this step.
+    pub fn set_pre_solve_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Arbiter, Option<&mut T>) -> bool {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.pre_solve = Some(Box::new(func));
+            (*raw.cp_handler).preSolveFunc = Some(pre_solve_trampoline::<T>);
+        }
+    }
+
+    // TODO: hide doc
+    /// Removes the pre-solve closure and returns it, so that a new closure
+    /// can call it.
+    pub unsafe fn take_pre_solve_func(&mut self) -> Option<FilterFunc<T>> {
+        (*self.raw.get()).pre_solve.take()
+    }
+
+    /// Sets the closure that is called every step while two shapes touch,
+    /// after the collision is solved.
+    pub fn set_post_solve_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Arbiter, Option<&mut T>) {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.post_solve = Some(Box::new(func));
+            (*raw.cp_handler).postSolveFunc = Some(post_solve_trampoline::<T>);
+        }
+    }
+
+    /// Sets the closure that is called when two shapes stop touching.
+    pub fn set_separate_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Arbiter, Option<&mut T>) {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.separate = Some(Box::new(func));
+            (*raw.cp_handler).separateFunc = Some(separate_trampoline::<T>);
+        }
+    }
+
+    /// Sets whether the begin, post-solve and separate callbacks of this
+    /// handler are recorded as `CollisionEvent`s.
+    ///
+    /// Events are recorded in addition to calling any closures, and can be
+    /// collected with `Space::drain_collision_events()`.
+    pub fn set_record_events(&mut self, record_events: bool) {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.record_events = record_events;
+            if record_events {
+                (*raw.cp_handler).beginFunc = Some(begin_trampoline::<T>);
+                (*raw.cp_handler).postSolveFunc = Some(post_solve_trampoline::<T>);
+                (*raw.cp_handler).separateFunc = Some(separate_trampoline::<T>);
+            }
+        }
+    }
+}
+
+impl <T: 'static + Any> CollisionHandler<T> {
+    /// Sets whether this handler reports the shapes that touch its first
+    /// collision type to the sensor tracker of the space.
+    ///
+    /// See `Space::track_sensors()`.
+    pub fn set_track_sensors(&mut self, track_sensors: bool) {
+        unsafe {
+            let raw = &mut *self.raw.get();
+            raw.track_sensors = track_sensors;
+            if track_sensors {
+                (*raw.cp_handler).beginFunc = Some(begin_trampoline::<T>);
+                (*raw.cp_handler).separateFunc = Some(separate_trampoline::<T>);
+            }
+        }
+    }
+}
+
+/// Copies the state of a collision into the event queue of its space.
+unsafe fn record_event(cp_arbiter: *mut chip::cpArbiter,
+                       cp_space: *mut chip::cpSpace,
+                       phase: CollisionPhase) {
+    let arbiter = Arbiter::from_cp_arbiter(cp_arbiter, cp_space, phase);
+    let shapes = match arbiter.shapes() {
+        Some(shapes) => shapes,
+        None => return
+    };
+    let event = CollisionEvent {
+        phase: phase,
+        shapes: shapes,
+        normal: arbiter.normal(),
+        contact_point_set: arbiter.contact_point_set(),
+        total_impulse: arbiter.total_impulse().ok()
+    };
+    space::push_collision_event(cp_space, event);
+}
+
+/// Calls a filtering closure, or chipmunk's default callback if there
+/// is none.
+///
+/// The closure is taken out of its slot while it runs, so that it may
+/// safely replace itself.
+unsafe fn call_filter_func<T: 'static + Any>(slot: *mut Option<FilterFunc<T>>,
+                                             default: chip::cpCollisionBeginFunc,
+                                             cp_arbiter: *mut chip::cpArbiter,
+                                             cp_space: *mut chip::cpSpace,
+                                             data: chip::cpDataPointer,
+                                             phase: CollisionPhase) -> chip::cpBool {
+    match (*slot).take() {
+        Some(mut func) => {
+            let mut arbiter = Arbiter::from_cp_arbiter(cp_arbiter, cp_space, phase);
+            let keep = func(&mut arbiter, space::find_user_data::<T>(cp_space));
+            if (*slot).is_none() {
+                *slot = Some(func);
+            }
+            if keep {1} else {0}
+        }
+        None => match default {
+            Some(default) => default(cp_arbiter, cp_space, data),
+            None => 1
+        }
+    }
+}
+
+/// Calls a notifying closure, or chipmunk's default callback if there
+/// is none.
+///
+/// See `call_filter_func`.
+unsafe fn call_notify_func<T: 'static + Any>(slot: *mut Option<NotifyFunc<T>>,
+                                             default: chip::cpCollisionPostSolveFunc,
+                                             cp_arbiter: *mut chip::cpArbiter,
+                                             cp_space: *mut chip::cpSpace,
+                                             data: chip::cpDataPointer,
+                                             phase: CollisionPhase) {
+    match (*slot).take() {
+        Some(mut func) => {
+            let mut arbiter = Arbiter::from_cp_arbiter(cp_arbiter, cp_space, phase);
+            func(&mut arbiter, space::find_user_data::<T>(cp_space));
+            if (*slot).is_none() {
+                *slot = Some(func);
+            }
+        }
+        None => if let Some(default) = default {
+            default(cp_arbiter, cp_space, data);
+        }
+    }
+}
+
+extern "C" fn begin_trampoline<T: 'static + Any>(cp_arbiter: *mut chip::cpArbiter,
+                                                 cp_space: *mut chip::cpSpace,
+                                                 data: chip::cpDataPointer) -> chip::cpBool {
+    unsafe {
+        let raw = data as *mut CollisionHandlerRaw<T>;
+        if (*raw).record_events {
+            record_event(cp_arbiter, cp_space, CollisionPhase::Begin);
+        }
+        if (*raw).track_sensors {
+            let arbiter = Arbiter::from_cp_arbiter(cp_arbiter, cp_space, CollisionPhase::Begin);
+            if let Some((sensor, other)) = arbiter.shapes() {
+                space::sensor_tracker(cp_space).enter(sensor, other);
+            }
+        }
+        call_filter_func(&mut (*raw).begin, (*raw).default_begin, cp_arbiter, cp_space, data,
+                         CollisionPhase::Begin)
+    }
+}
+
+extern "C" fn pre_solve_trampoline<T: 'static + Any>(cp_arbiter: *mut chip::cpArbiter,
+                                                     cp_space: *mut chip::cpSpace,
+                                                     data: chip::cpDataPointer) -> chip::cpBool {
+    unsafe {
+        let raw = data as *mut CollisionHandlerRaw<T>;
+        call_filter_func(&mut (*raw).pre_solve, (*raw).default_pre_solve, cp_arbiter, cp_space, data,
+                         CollisionPhase::PreSolve)
+    }
+}
+
+extern "C" fn post_solve_trampoline<T: 'static + Any>(cp_arbiter: *mut chip::cpArbiter,
+                                                      cp_space: *mut chip::cpSpace,
+                                                      data: chip::cpDataPointer) {
+    unsafe {
+        let raw = data as *mut CollisionHandlerRaw<T>;
+        if (*raw).record_events {
+            record_event(cp_arbiter, cp_space, CollisionPhase::PostSolve);
+        }
+        call_notify_func(&mut (*raw).post_solve, (*raw).default_post_solve, cp_arbiter, cp_space, data,
+                         CollisionPhase::PostSolve)
+    }
+}
+
+extern "C" fn separate_trampoline<T: 'static + Any>(cp_arbiter: *mut chip::cpArbiter,
+                                                    cp_space: *mut chip::cpSpace,
+                                                    data: chip::cpDataPointer) {
+    unsafe {
+        let raw = data as *mut CollisionHandlerRaw<T>;
+        if (*raw).record_events {
+            record_event(cp_arbiter, cp_space, CollisionPhase::Separate);
+        }
+        if (*raw).track_sensors {
+            let arbiter = Arbiter::from_cp_arbiter(cp_arbiter, cp_space, CollisionPhase::Separate);
+            if let Some((sensor, other)) = arbiter.shapes() {
+                space::sensor_tracker(cp_space).exit(sensor, other);
+            }
+        }
+        call_notify_func(&mut (*raw).separate, (*raw).default_separate, cp_arbiter, cp_space, data,
+                         CollisionPhase::Separate);
+        space::separate_arbiter(cp_space, cp_arbiter);
+    }
+}
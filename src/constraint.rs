@@ -0,0 +1,1197 @@
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::UnsafeCell;
+use std::mem::{transmute, zeroed};
+use std::marker::PhantomData;
+
+use super::user_data::UserData;
+use super::body::Body;
+use super::space;
+use super::vect::Vect;
+use super::angle::Radians;
+
+use chip;
+
+use void::Void;
+
+/// Runs `$body` with `$raw` bound to a mutable reference to the raw
+/// constraint behind any variant of `Constraint`.
+macro_rules! with_raw {
+    ($this:expr, $raw:ident => $body:expr) => {
+        match *$this {
+            Constraint::Pin(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Slide(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Pivot(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Groove(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Spring(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::RotarySpring(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Motor(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Gear(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::Ratchet(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+            Constraint::RotaryLimit(ref c) => { let $raw = unsafe { &mut *c.raw.get() }; $body }
+        }
+    };
+}
+
+pub enum Constraint<T=Void> {
+    Pin(PinJoint<T>),
+    Slide(SlideJoint<T>),
+    Pivot(PivotJoint<T>),
+    Groove(GrooveJoint<T>),
+    Spring(DampedSpring<T>),
+    RotarySpring(DampedRotarySpring<T>),
+    Motor(SimpleMotor<T>),
+    Gear(GearJoint<T>),
+    Ratchet(RatchetJoint<T>),
+    RotaryLimit(RotaryLimitJoint<T>)
+}
+
+pub struct PinJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpPinJoint, T>>>
+}
+
+pub struct SlideJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpSlideJoint, T>>>
+}
+
+pub struct PivotJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpPivotJoint, T>>>
+}
+
+pub struct GrooveJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpGrooveJoint, T>>>
+}
+
+pub struct DampedSpring<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpDampedSpring, T>>>
+}
+
+pub struct DampedRotarySpring<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpDampedRotarySpring, T>>>
+}
+
+pub struct SimpleMotor<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpSimpleMotor, T>>>
+}
+
+pub struct GearJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpGearJoint, T>>>
+}
+
+pub struct RatchetJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpRatchetJoint, T>>>
+}
+
+pub struct RotaryLimitJoint<T=Void> {
+    raw: Rc<UnsafeCell<ConstraintRaw<chip::cpRotaryLimitJoint, T>>>
+}
+
+/// A closure that chipmunk calls before or after solving a constraint.
+///
+/// It receives the constraint along with the two bodies that it connects.
+pub type SolveFunc<T> = Box<FnMut(&mut Constraint<T>, &mut Body, &mut Body)>;
+
+/// The raw storage for every kind of constraint.
+///
+/// `C` is the chipmunk struct for the specific joint (`cpPinJoint`,
+/// `cpSlideJoint`, ...), all of which begin with a `cpConstraint`.
+struct ConstraintRaw<C, T=Void> {
+    cp_constraint: C,
+    user_data: Option<Box<Any>>,
+    /// Replaces the force (or torque) law of a damped spring.  Unused by
+    /// every other kind of constraint.
+    spring_func: Option<Box<FnMut(f64) -> f64>>,
+    break_threshold: Option<f64>,
+    pre_solve: Option<SolveFunc<T>>,
+    post_solve: Option<SolveFunc<T>>,
+    attached_bodies: (Body<Void>, Body<Void>),
+    _phantom: PhantomData<T>
+}
+
+impl <T: 'static + Any> UserData<T> for Constraint<T> {
+    fn get_userdata_box(&self) -> &Option<Box<Any>> {
+        with_raw!(self, r => &r.user_data)
+    }
+
+    fn get_userdata_mut_box(&mut self) -> &mut Option<Box<Any>> {
+        with_raw!(self, r => &mut r.user_data)
+    }
+}
+
+impl Constraint<Void> {
+    /// Creates a joint that keeps the anchor points on both bodies at a
+    /// fixed distance, as if they were connected by a solid pin.
+    ///
+    /// The distance is measured from the current positions of the anchors.
+    pub fn new_pin_joint<A, B, P: Into<Vect>, Q: Into<Vect>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                             anchor_a: P, anchor_b: Q) -> Constraint<Void> {
+        let (anchor_a, anchor_b): (Vect, Vect) = (anchor_a.into(), anchor_b.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpPinJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                 anchor_a.into(),
+                                 anchor_b.into());
+        }
+
+        Constraint::Pin(PinJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint that keeps the distance between the anchor points
+    /// on both bodies between `min` and `max`.
+    pub fn new_slide_joint<A, B, P: Into<Vect>, Q: Into<Vect>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                               anchor_a: P, anchor_b: Q,
+                                                               min: f64, max: f64) -> Constraint<Void> {
+        let (anchor_a, anchor_b): (Vect, Vect) = (anchor_a.into(), anchor_b.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpSlideJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                   anchor_a.into(),
+                                   anchor_b.into(),
+                                   min, max);
+        }
+
+        Constraint::Slide(SlideJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint that lets both bodies rotate around a shared pivot.
+    ///
+    /// The anchors are given in the local coordinates of each body and
+    /// should be placed at the same point in world space.
+    pub fn new_pivot_joint<A, B, P: Into<Vect>, Q: Into<Vect>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                               anchor_a: P, anchor_b: Q) -> Constraint<Void> {
+        let (anchor_a, anchor_b): (Vect, Vect) = (anchor_a.into(), anchor_b.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpPivotJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                   anchor_a.into(),
+                                   anchor_b.into());
+        }
+
+        Constraint::Pivot(PivotJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint where the anchor on body `b` slides along a groove
+    /// that runs from `groove_a` to `groove_b` on body `a`.
+    pub fn new_groove_joint<A, B, P: Into<Vect>, Q: Into<Vect>, V: Into<Vect>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                                               groove_a: P, groove_b: Q,
+                                                                               anchor_b: V) -> Constraint<Void> {
+        let (groove_a, groove_b, anchor_b): (Vect, Vect, Vect) =
+            (groove_a.into(), groove_b.into(), anchor_b.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpGrooveJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                    groove_a.into(),
+                                    groove_b.into(),
+                                    anchor_b.into());
+        }
+
+        Constraint::Groove(GrooveJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a spring between the anchor points on both bodies.
+    ///
+    /// The spring pushes or pulls the anchors towards `rest_length` with a
+    /// force proportional to `stiffness`, and `damping` softens the motion.
+    pub fn new_damped_spring<A, B, P: Into<Vect>, Q: Into<Vect>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                                 anchor_a: P, anchor_b: Q,
+                                                                 rest_length: f64, stiffness: f64,
+                                                                 damping: f64) -> Constraint<Void> {
+        let (anchor_a, anchor_b): (Vect, Vect) = (anchor_a.into(), anchor_b.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpDampedSpringInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                     anchor_a.into(),
+                                     anchor_b.into(),
+                                     rest_length, stiffness, damping);
+        }
+
+        Constraint::Spring(DampedSpring{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a spring that works on the relative angle of both bodies.
+    ///
+    /// `rest_angle` is the relative angle that the spring tries to
+    /// maintain.
+    pub fn new_damped_rotary_spring<A, B, R: Into<Radians>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                            rest_angle: R, stiffness: f64,
+                                                            damping: f64) -> Constraint<Void> {
+        let rest_angle: Radians = rest_angle.into();
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpDampedRotarySpringInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                           rest_angle.0, stiffness, damping);
+        }
+
+        Constraint::RotarySpring(DampedRotarySpring{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a motor that keeps the relative angular velocity of both
+    /// bodies at `rate` (per second).
+    pub fn new_simple_motor<A, B, R: Into<Radians>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                    rate: R) -> Constraint<Void> {
+        let rate: Radians = rate.into();
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpSimpleMotorInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(), rate.0);
+        }
+
+        Constraint::Motor(SimpleMotor{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint that keeps the angular velocity ratio of both
+    /// bodies constant.
+    ///
+    /// `phase` is the initial angular offset.
+    pub fn new_gear_joint<A, B, R: Into<Radians>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                  phase: R, ratio: f64) -> Constraint<Void> {
+        let phase: Radians = phase.into();
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpGearJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                  phase.0, ratio);
+        }
+
+        Constraint::Gear(GearJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint that works like a socket wrench, only letting the
+    /// bodies turn in one direction.
+    ///
+    /// `phase` is the initial offset and `ratchet` the distance between
+    /// clicks.
+    pub fn new_ratchet_joint<A, B, R: Into<Radians>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                     phase: R, ratchet: R) -> Constraint<Void> {
+        let (phase, ratchet): (Radians, Radians) = (phase.into(), ratchet.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpRatchetJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                     phase.0, ratchet.0);
+        }
+
+        Constraint::Ratchet(RatchetJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+
+    /// Creates a joint that keeps the relative angle of both bodies
+    /// between `min` and `max`.
+    pub fn new_rotary_limit_joint<A, B, R: Into<Radians>>(a: &mut Body<A>, b: &mut Body<B>,
+                                                          min: R, max: R) -> Constraint<Void> {
+        let (min, max): (Radians, Radians) = (min.into(), max.into());
+        let mut raw = ConstraintRaw::new(a, b);
+        unsafe {
+            chip::cpRotaryLimitJointInit(&mut raw.cp_constraint, a.get_cp_body(), b.get_cp_body(),
+                                         min.0, max.0);
+        }
+
+        Constraint::RotaryLimit(RotaryLimitJoint{ raw: Rc::new(UnsafeCell::new(raw)) })
+    }
+}
+
+impl <T> Constraint<T> {
+    pub unsafe fn get_cp_constraint(&self) -> *const chip::cpConstraint {
+        with_raw!(self, r => r.get_cp_constraint())
+    }
+
+    pub unsafe fn get_cp_constraint_mut(&mut self) -> *mut chip::cpConstraint {
+        transmute(self.get_cp_constraint())
+    }
+
+    pub unsafe fn duplicate(&self) -> Constraint<Void> {
+        match *self {
+            Constraint::Pin(ref c) => Constraint::Pin(transmute(PinJoint{raw: c.raw.clone()})),
+            Constraint::Slide(ref c) => Constraint::Slide(transmute(SlideJoint{raw: c.raw.clone()})),
+            Constraint::Pivot(ref c) => Constraint::Pivot(transmute(PivotJoint{raw: c.raw.clone()})),
+            Constraint::Groove(ref c) => Constraint::Groove(transmute(GrooveJoint{raw: c.raw.clone()})),
+            Constraint::Spring(ref c) => Constraint::Spring(transmute(DampedSpring{raw: c.raw.clone()})),
+            Constraint::RotarySpring(ref c) => Constraint::RotarySpring(transmute(DampedRotarySpring{raw: c.raw.clone()})),
+            Constraint::Motor(ref c) => Constraint::Motor(transmute(SimpleMotor{raw: c.raw.clone()})),
+            Constraint::Gear(ref c) => Constraint::Gear(transmute(GearJoint{raw: c.raw.clone()})),
+            Constraint::Ratchet(ref c) => Constraint::Ratchet(transmute(RatchetJoint{raw: c.raw.clone()})),
+            Constraint::RotaryLimit(ref c) => Constraint::RotaryLimit(transmute(RotaryLimitJoint{raw: c.raw.clone()})),
+        }
+    }
+
+    /// Returns the maximum force that the constraint can use to act on
+    /// the two bodies.
+    ///
+    /// Defaults to infinity.
+    pub fn max_force(&self) -> f64 {
+        unsafe {
+            chip::cpConstraintGetMaxForce(self.get_cp_constraint())
+        }
+    }
+
+    /// Returns the percentage of joint error that remains unfixed after
+    /// a second.
+    ///
+    /// Defaults to `pow(1 - 0.1, 60)` meaning that the constraint fixes
+    /// 10% of its error each frame at 60Hz.
+    pub fn error_bias(&self) -> f64 {
+        unsafe {
+            chip::cpConstraintGetErrorBias(self.get_cp_constraint())
+        }
+    }
+
+    /// Returns the maximum speed at which the constraint can apply error
+    /// correction.
+    ///
+    /// Defaults to infinity.
+    pub fn max_bias(&self) -> f64 {
+        unsafe {
+            chip::cpConstraintGetMaxBias(self.get_cp_constraint())
+        }
+    }
+
+    /// Returns true if the two bodies connected by this constraint are
+    /// allowed to collide with each other.
+    ///
+    /// Defaults to true.
+    pub fn collide_bodies(&self) -> bool {
+        unsafe {
+            let r = chip::cpConstraintGetCollideBodies(self.get_cp_constraint());
+            if r == 0 {false} else {true}
+        }
+    }
+
+    /// Returns the impulse that this constraint applied during the last
+    /// step.
+    ///
+    /// Divide by the timestep to get the force that was applied.
+    pub fn impulse(&self) -> f64 {
+        unsafe {
+            chip::cpConstraintGetImpulse(self.get_cp_constraint())
+        }
+    }
+
+    /// Returns the force above which the space breaks this constraint.
+    ///
+    /// See `set_break_threshold()`.
+    pub fn break_threshold(&self) -> Option<f64> {
+        with_raw!(self, r => r.break_threshold)
+    }
+
+    /// Sets the maximum force that the constraint can use.  See `max_force()`.
+    pub fn set_max_force(&mut self, max_force: f64) {
+        unsafe {
+            chip::cpConstraintSetMaxForce(self.get_cp_constraint_mut(), max_force);
+        }
+    }
+
+    /// Sets the joint error left unfixed after a second.  See `error_bias()`.
+    pub fn set_error_bias(&mut self, error_bias: f64) {
+        unsafe {
+            chip::cpConstraintSetErrorBias(self.get_cp_constraint_mut(), error_bias);
+        }
+    }
+
+    /// Sets the maximum speed of error correction.  See `max_bias()`.
+    pub fn set_max_bias(&mut self, max_bias: f64) {
+        unsafe {
+            chip::cpConstraintSetMaxBias(self.get_cp_constraint_mut(), max_bias);
+        }
+    }
+
+    /// Sets whether the two bodies may collide.  See `collide_bodies()`.
+    pub fn set_collide_bodies(&mut self, collide_bodies: bool) {
+        unsafe {
+            let v = if collide_bodies {1} else {0};
+            chip::cpConstraintSetCollideBodies(self.get_cp_constraint_mut(), v);
+        }
+    }
+
+    /// Makes this constraint breakable.
+    ///
+    /// After every `Space::step()`, a constraint whose impulse divided by
+    /// the timestep exceeds `threshold` is removed from the space and can
+    /// be collected with `Space::drain_broken_constraints()`.  Passing
+    /// `None` makes the constraint unbreakable again, which is the default.
+    pub fn set_break_threshold(&mut self, threshold: Option<f64>) {
+        with_raw!(self, r => r.break_threshold = threshold)
+    }
+
+    /// Sets a closure that is called every step before the constraint is
+    /// solved.
+    ///
+    /// The closure receives this constraint and the two bodies that it
+    /// connects.  It is only called while the constraint is in a space.
+    pub fn set_pre_solve_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Constraint<T>, &mut Body, &mut Body) {
+        with_raw!(self, r => r.set_pre_solve_func(Box::new(func)))
+    }
+
+    /// Sets a closure that is called every step after the constraint is
+    /// solved.
+    ///
+    /// See `set_pre_solve_func()`.
+    pub fn set_post_solve_func<F>(&mut self, func: F)
+    where F: 'static + FnMut(&mut Constraint<T>, &mut Body, &mut Body) {
+        with_raw!(self, r => r.set_post_solve_func(Box::new(func)))
+    }
+
+    /// Removes the closure set with `set_pre_solve_func()`.
+    pub fn clear_pre_solve_func(&mut self) {
+        with_raw!(self, r => r.clear_pre_solve_func())
+    }
+
+    /// Removes the closure set with `set_post_solve_func()`.
+    pub fn clear_post_solve_func(&mut self) {
+        with_raw!(self, r => r.clear_post_solve_func())
+    }
+
+    /// Returns true if the force applied during a step of length
+    /// `timestep` exceeded the break threshold.
+    pub fn exceeds_break_threshold(&self, timestep: f64) -> bool {
+        match self.break_threshold() {
+            Some(threshold) if timestep > 0.0 => self.impulse() / timestep > threshold,
+            _ => false
+        }
+    }
+}
+
+impl <T> PinJoint<T> {
+    forward!(anchor_a(&self) -> Vect,
+    /// Returns the anchor point on body `a` in local coordinates.
+    );
+
+    forward!(anchor_b(&self) -> Vect,
+    /// Returns the anchor point on body `b` in local coordinates.
+    );
+
+    forward!(dist(&self) -> f64,
+    /// Returns the distance that the joint keeps between both anchors.
+    );
+
+    /// Sets the anchor point on body `a` in local coordinates.
+    pub fn set_anchor_a<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_a(anchor.into())
+        }
+    }
+
+    /// Sets the anchor point on body `b` in local coordinates.
+    pub fn set_anchor_b<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_b(anchor.into())
+        }
+    }
+
+    forward!(set_dist(&mut self, dist: f64) -> (),
+    /// Sets the distance that the joint keeps between both anchors.
+    );
+}
+
+impl <T> SlideJoint<T> {
+    forward!(anchor_a(&self) -> Vect,
+    /// Returns the anchor point on body `a` in local coordinates.
+    );
+
+    forward!(anchor_b(&self) -> Vect,
+    /// Returns the anchor point on body `b` in local coordinates.
+    );
+
+    forward!(min(&self) -> f64,
+    /// Returns the minimum distance between both anchors.
+    );
+
+    forward!(max(&self) -> f64,
+    /// Returns the maximum distance between both anchors.
+    );
+
+    /// Sets the anchor point on body `a` in local coordinates.
+    pub fn set_anchor_a<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_a(anchor.into())
+        }
+    }
+
+    /// Sets the anchor point on body `b` in local coordinates.
+    pub fn set_anchor_b<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_b(anchor.into())
+        }
+    }
+
+    forward!(set_min(&mut self, min: f64) -> (),
+    /// Sets the minimum distance between both anchors.
+    );
+
+    forward!(set_max(&mut self, max: f64) -> (),
+    /// Sets the maximum distance between both anchors.
+    );
+}
+
+impl <T> PivotJoint<T> {
+    forward!(anchor_a(&self) -> Vect,
+    /// Returns the pivot point on body `a` in local coordinates.
+    );
+
+    forward!(anchor_b(&self) -> Vect,
+    /// Returns the pivot point on body `b` in local coordinates.
+    );
+
+    /// Sets the pivot point on body `a` in local coordinates.
+    pub fn set_anchor_a<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_a(anchor.into())
+        }
+    }
+
+    /// Sets the pivot point on body `b` in local coordinates.
+    pub fn set_anchor_b<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_b(anchor.into())
+        }
+    }
+}
+
+impl <T> GrooveJoint<T> {
+    forward!(groove_a(&self) -> Vect,
+    /// Returns the start of the groove on body `a` in local coordinates.
+    );
+
+    forward!(groove_b(&self) -> Vect,
+    /// Returns the end of the groove on body `a` in local coordinates.
+    );
+
+    forward!(anchor_b(&self) -> Vect,
+    /// Returns the anchor point on body `b` in local coordinates.
+    );
+
+    /// Sets the start of the groove on body `a` in local coordinates.
+    pub fn set_groove_a<P: Into<Vect>>(&mut self, groove: P) {
+        unsafe {
+            (*self.raw.get()).set_groove_a(groove.into())
+        }
+    }
+
+    /// Sets the end of the groove on body `a` in local coordinates.
+    pub fn set_groove_b<P: Into<Vect>>(&mut self, groove: P) {
+        unsafe {
+            (*self.raw.get()).set_groove_b(groove.into())
+        }
+    }
+
+    /// Sets the anchor point on body `b` in local coordinates.
+    pub fn set_anchor_b<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_b(anchor.into())
+        }
+    }
+}
+
+impl <T> DampedSpring<T> {
+    forward!(anchor_a(&self) -> Vect,
+    /// Returns the anchor point on body `a` in local coordinates.
+    );
+
+    forward!(anchor_b(&self) -> Vect,
+    /// Returns the anchor point on body `b` in local coordinates.
+    );
+
+    forward!(rest_length(&self) -> f64,
+    /// Returns the distance that the spring wants to be at.
+    );
+
+    forward!(stiffness(&self) -> f64,
+    /// Returns the spring constant (Young's modulus).
+    );
+
+    forward!(damping(&self) -> f64,
+    /// Returns how soft to make the damping of the spring.
+    );
+
+    /// Sets the anchor point on body `a` in local coordinates.
+    pub fn set_anchor_a<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_a(anchor.into())
+        }
+    }
+
+    /// Sets the anchor point on body `b` in local coordinates.
+    pub fn set_anchor_b<P: Into<Vect>>(&mut self, anchor: P) {
+        unsafe {
+            (*self.raw.get()).set_anchor_b(anchor.into())
+        }
+    }
+
+    forward!(set_rest_length(&mut self, rest_length: f64) -> (),
+    /// Sets the distance that the spring wants to be at.
+    );
+
+    forward!(set_stiffness(&mut self, stiffness: f64) -> (),
+    /// Sets the spring constant (Young's modulus).
+    );
+
+    forward!(set_damping(&mut self, damping: f64) -> (),
+    /// Sets how soft to make the damping of the spring.
+    );
+
+    /// Replaces the force law of the spring.
+    ///
+    /// `func` receives the current distance between the anchors and returns
+    /// the force that the spring applies.  Positive values push the anchors
+    /// apart.
+    pub fn set_spring_force_func<F: 'static + FnMut(f64) -> f64>(&mut self, func: F) {
+        unsafe {
+            (*self.raw.get()).set_spring_force_func(Box::new(func));
+        }
+    }
+
+    forward!(clear_spring_force_func(&mut self) -> (),
+    /// Restores the default linear force law of the spring.
+    );
+}
+
+impl <T> DampedRotarySpring<T> {
+    forward!(rest_angle(&self) -> Radians,
+    /// Returns the relative angle that the spring wants to be at.
+    );
+
+    forward!(stiffness(&self) -> f64,
+    /// Returns the spring constant (Young's modulus).
+    );
+
+    forward!(damping(&self) -> f64,
+    /// Returns how soft to make the damping of the spring.
+    );
+
+    /// Sets the relative angle that the spring wants to be at.
+    pub fn set_rest_angle<A: Into<Radians>>(&mut self, angle: A) {
+        unsafe {
+            (*self.raw.get()).set_rest_angle(angle.into())
+        }
+    }
+
+    forward!(set_stiffness(&mut self, stiffness: f64) -> (),
+    /// Sets the spring constant (Young's modulus).
+    );
+
+    forward!(set_damping(&mut self, damping: f64) -> (),
+    /// Sets how soft to make the damping of the spring.
+    );
+
+    /// Replaces the torque law of the spring.
+    ///
+    /// `func` receives the current relative angle of the bodies in radians
+    /// and returns the torque that the spring applies.
+    pub fn set_spring_torque_func<F: 'static + FnMut(f64) -> f64>(&mut self, func: F) {
+        unsafe {
+            (*self.raw.get()).set_spring_torque_func(Box::new(func));
+        }
+    }
+
+    forward!(clear_spring_torque_func(&mut self) -> (),
+    /// Restores the default linear torque law of the spring.
+    );
+}
+
+impl <T> SimpleMotor<T> {
+    forward!(rate(&self) -> Radians,
+    /// Returns the relative angular velocity (per second) that the motor
+    /// drives.
+    );
+
+    /// Sets the relative angular velocity (per second) that the motor
+    /// drives.
+    pub fn set_rate<A: Into<Radians>>(&mut self, rate: A) {
+        unsafe {
+            (*self.raw.get()).set_rate(rate.into())
+        }
+    }
+}
+
+impl <T> GearJoint<T> {
+    forward!(phase(&self) -> Radians,
+    /// Returns the angular offset of the gears.
+    );
+
+    forward!(ratio(&self) -> f64,
+    /// Returns the ratio of the angular velocities of both bodies.
+    );
+
+    /// Sets the angular offset of the gears.
+    pub fn set_phase<A: Into<Radians>>(&mut self, phase: A) {
+        unsafe {
+            (*self.raw.get()).set_phase(phase.into())
+        }
+    }
+
+    forward!(set_ratio(&mut self, ratio: f64) -> (),
+    /// Sets the ratio of the angular velocities of both bodies.
+    );
+}
+
+impl <T> RatchetJoint<T> {
+    forward!(angle(&self) -> Radians,
+    /// Returns the current angle of the ratchet.
+    );
+
+    forward!(phase(&self) -> Radians,
+    /// Returns the angular offset of the ratchet.
+    );
+
+    forward!(ratchet(&self) -> Radians,
+    /// Returns the distance between clicks.
+    );
+
+    /// Sets the current angle of the ratchet.
+    pub fn set_angle<A: Into<Radians>>(&mut self, angle: A) {
+        unsafe {
+            (*self.raw.get()).set_angle(angle.into())
+        }
+    }
+
+    /// Sets the angular offset of the ratchet.
+    pub fn set_phase<A: Into<Radians>>(&mut self, phase: A) {
+        unsafe {
+            (*self.raw.get()).set_phase(phase.into())
+        }
+    }
+
+    /// Sets the distance between clicks.
+    pub fn set_ratchet<A: Into<Radians>>(&mut self, ratchet: A) {
+        unsafe {
+            (*self.raw.get()).set_ratchet(ratchet.into())
+        }
+    }
+}
+
+impl <T> RotaryLimitJoint<T> {
+    forward!(min(&self) -> Radians,
+    /// Returns the minimum relative angle of the bodies.
+    );
+
+    forward!(max(&self) -> Radians,
+    /// Returns the maximum relative angle of the bodies.
+    );
+
+    /// Sets the minimum relative angle of the bodies.
+    pub fn set_min<A: Into<Radians>>(&mut self, min: A) {
+        unsafe {
+            (*self.raw.get()).set_min(min.into())
+        }
+    }
+
+    /// Sets the maximum relative angle of the bodies.
+    pub fn set_max<A: Into<Radians>>(&mut self, max: A) {
+        unsafe {
+            (*self.raw.get()).set_max(max.into())
+        }
+    }
+}
+
+impl <C, T> ConstraintRaw<C, T> {
+    fn new<A, B>(a: &mut Body<A>, b: &mut Body<B>) -> ConstraintRaw<C, T> {
+        unsafe {
+            ConstraintRaw {
+                cp_constraint: zeroed(),
+                user_data: None,
+                spring_func: None,
+                break_threshold: None,
+                pre_solve: None,
+                post_solve: None,
+                attached_bodies: (a.duplicate(), b.duplicate()),
+                _phantom: PhantomData
+            }
+        }
+    }
+
+    fn get_cp_constraint(&self) -> *const chip::cpConstraint {
+        &self.cp_constraint as *const C as *const chip::cpConstraint
+    }
+
+    fn get_cp_constraint_mut(&mut self) -> *mut chip::cpConstraint {
+        &mut self.cp_constraint as *mut C as *mut chip::cpConstraint
+    }
+
+    /// Points the user data of the chipmunk constraint at this struct so
+    /// that trampolines can find their closures.
+    ///
+    /// Must only be called once the raw constraint has been moved into
+    /// its final location.
+    fn bind_user_data(&mut self) {
+        let ptr = self as *mut Self as chip::cpDataPointer;
+        unsafe { chip::cpConstraintSetUserData(self.get_cp_constraint_mut(), ptr) }
+    }
+
+    fn set_pre_solve_func(&mut self, func: SolveFunc<T>) {
+        self.pre_solve = Some(func);
+        self.bind_user_data();
+        unsafe {
+            chip::cpConstraintSetPreSolveFunc(self.get_cp_constraint_mut(),
+                                              Some(pre_solve_trampoline::<C, T>));
+        }
+    }
+
+    fn set_post_solve_func(&mut self, func: SolveFunc<T>) {
+        self.post_solve = Some(func);
+        self.bind_user_data();
+        unsafe {
+            chip::cpConstraintSetPostSolveFunc(self.get_cp_constraint_mut(),
+                                               Some(post_solve_trampoline::<C, T>));
+        }
+    }
+
+    fn clear_pre_solve_func(&mut self) {
+        self.pre_solve = None;
+        unsafe {
+            chip::cpConstraintSetPreSolveFunc(self.get_cp_constraint_mut(), None);
+        }
+    }
+
+    fn clear_post_solve_func(&mut self) {
+        self.post_solve = None;
+        unsafe {
+            chip::cpConstraintSetPostSolveFunc(self.get_cp_constraint_mut(), None);
+        }
+    }
+
+    /// Calls `func` with handles to this constraint and its bodies.
+    ///
+    /// The handle to the constraint is looked up in `cp_space`, so nothing
+    /// is called if the constraint isn't part of a space.
+    unsafe fn call_solve_func(&mut self, func: &mut SolveFunc<T>, cp_space: *mut chip::cpSpace) {
+        if let Some(constraint) = space::find_constraint(cp_space, self.get_cp_constraint()) {
+            let mut constraint: Constraint<T> = transmute(constraint);
+            let mut a = self.attached_bodies.0.duplicate();
+            let mut b = self.attached_bodies.1.duplicate();
+            func(&mut constraint, &mut a, &mut b);
+        }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpPinJoint, T> {
+    fn anchor_a(&self) -> Vect {
+        unsafe { chip::cpPinJointGetAnchorA(self.get_cp_constraint()).into() }
+    }
+
+    fn anchor_b(&self) -> Vect {
+        unsafe { chip::cpPinJointGetAnchorB(self.get_cp_constraint()).into() }
+    }
+
+    fn dist(&self) -> f64 {
+        unsafe { chip::cpPinJointGetDist(self.get_cp_constraint()) }
+    }
+
+    fn set_anchor_a(&mut self, anchor: Vect) {
+        unsafe { chip::cpPinJointSetAnchorA(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_anchor_b(&mut self, anchor: Vect) {
+        unsafe { chip::cpPinJointSetAnchorB(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_dist(&mut self, dist: f64) {
+        unsafe { chip::cpPinJointSetDist(self.get_cp_constraint_mut(), dist) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpSlideJoint, T> {
+    fn anchor_a(&self) -> Vect {
+        unsafe { chip::cpSlideJointGetAnchorA(self.get_cp_constraint()).into() }
+    }
+
+    fn anchor_b(&self) -> Vect {
+        unsafe { chip::cpSlideJointGetAnchorB(self.get_cp_constraint()).into() }
+    }
+
+    fn min(&self) -> f64 {
+        unsafe { chip::cpSlideJointGetMin(self.get_cp_constraint()) }
+    }
+
+    fn max(&self) -> f64 {
+        unsafe { chip::cpSlideJointGetMax(self.get_cp_constraint()) }
+    }
+
+    fn set_anchor_a(&mut self, anchor: Vect) {
+        unsafe { chip::cpSlideJointSetAnchorA(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_anchor_b(&mut self, anchor: Vect) {
+        unsafe { chip::cpSlideJointSetAnchorB(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_min(&mut self, min: f64) {
+        unsafe { chip::cpSlideJointSetMin(self.get_cp_constraint_mut(), min) }
+    }
+
+    fn set_max(&mut self, max: f64) {
+        unsafe { chip::cpSlideJointSetMax(self.get_cp_constraint_mut(), max) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpPivotJoint, T> {
+    fn anchor_a(&self) -> Vect {
+        unsafe { chip::cpPivotJointGetAnchorA(self.get_cp_constraint()).into() }
+    }
+
+    fn anchor_b(&self) -> Vect {
+        unsafe { chip::cpPivotJointGetAnchorB(self.get_cp_constraint()).into() }
+    }
+
+    fn set_anchor_a(&mut self, anchor: Vect) {
+        unsafe { chip::cpPivotJointSetAnchorA(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_anchor_b(&mut self, anchor: Vect) {
+        unsafe { chip::cpPivotJointSetAnchorB(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpGrooveJoint, T> {
+    fn groove_a(&self) -> Vect {
+        unsafe { chip::cpGrooveJointGetGrooveA(self.get_cp_constraint()).into() }
+    }
+
+    fn groove_b(&self) -> Vect {
+        unsafe { chip::cpGrooveJointGetGrooveB(self.get_cp_constraint()).into() }
+    }
+
+    fn anchor_b(&self) -> Vect {
+        unsafe { chip::cpGrooveJointGetAnchorB(self.get_cp_constraint()).into() }
+    }
+
+    fn set_groove_a(&mut self, groove: Vect) {
+        unsafe { chip::cpGrooveJointSetGrooveA(self.get_cp_constraint_mut(), groove.into()) }
+    }
+
+    fn set_groove_b(&mut self, groove: Vect) {
+        unsafe { chip::cpGrooveJointSetGrooveB(self.get_cp_constraint_mut(), groove.into()) }
+    }
+
+    fn set_anchor_b(&mut self, anchor: Vect) {
+        unsafe { chip::cpGrooveJointSetAnchorB(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpDampedSpring, T> {
+    fn anchor_a(&self) -> Vect {
+        unsafe { chip::cpDampedSpringGetAnchorA(self.get_cp_constraint()).into() }
+    }
+
+    fn anchor_b(&self) -> Vect {
+        unsafe { chip::cpDampedSpringGetAnchorB(self.get_cp_constraint()).into() }
+    }
+
+    fn rest_length(&self) -> f64 {
+        unsafe { chip::cpDampedSpringGetRestLength(self.get_cp_constraint()) }
+    }
+
+    fn stiffness(&self) -> f64 {
+        unsafe { chip::cpDampedSpringGetStiffness(self.get_cp_constraint()) }
+    }
+
+    fn damping(&self) -> f64 {
+        unsafe { chip::cpDampedSpringGetDamping(self.get_cp_constraint()) }
+    }
+
+    fn set_anchor_a(&mut self, anchor: Vect) {
+        unsafe { chip::cpDampedSpringSetAnchorA(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_anchor_b(&mut self, anchor: Vect) {
+        unsafe { chip::cpDampedSpringSetAnchorB(self.get_cp_constraint_mut(), anchor.into()) }
+    }
+
+    fn set_rest_length(&mut self, rest_length: f64) {
+        unsafe { chip::cpDampedSpringSetRestLength(self.get_cp_constraint_mut(), rest_length) }
+    }
+
+    fn set_stiffness(&mut self, stiffness: f64) {
+        unsafe { chip::cpDampedSpringSetStiffness(self.get_cp_constraint_mut(), stiffness) }
+    }
+
+    fn set_damping(&mut self, damping: f64) {
+        unsafe { chip::cpDampedSpringSetDamping(self.get_cp_constraint_mut(), damping) }
+    }
+
+    fn set_spring_force_func(&mut self, func: Box<FnMut(f64) -> f64>) {
+        self.spring_func = Some(func);
+        self.bind_user_data();
+        unsafe {
+            chip::cpDampedSpringSetSpringForceFunc(self.get_cp_constraint_mut(),
+                                                   Some(spring_force_trampoline::<T>));
+        }
+    }
+
+    fn clear_spring_force_func(&mut self) {
+        self.spring_func = None;
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpDampedRotarySpring, T> {
+    fn rest_angle(&self) -> Radians {
+        unsafe { Radians(chip::cpDampedRotarySpringGetRestAngle(self.get_cp_constraint())) }
+    }
+
+    fn stiffness(&self) -> f64 {
+        unsafe { chip::cpDampedRotarySpringGetStiffness(self.get_cp_constraint()) }
+    }
+
+    fn damping(&self) -> f64 {
+        unsafe { chip::cpDampedRotarySpringGetDamping(self.get_cp_constraint()) }
+    }
+
+    fn set_rest_angle(&mut self, angle: Radians) {
+        unsafe { chip::cpDampedRotarySpringSetRestAngle(self.get_cp_constraint_mut(), angle.0) }
+    }
+
+    fn set_stiffness(&mut self, stiffness: f64) {
+        unsafe { chip::cpDampedRotarySpringSetStiffness(self.get_cp_constraint_mut(), stiffness) }
+    }
+
+    fn set_damping(&mut self, damping: f64) {
+        unsafe { chip::cpDampedRotarySpringSetDamping(self.get_cp_constraint_mut(), damping) }
+    }
+
+    fn set_spring_torque_func(&mut self, func: Box<FnMut(f64) -> f64>) {
+        self.spring_func = Some(func);
+        self.bind_user_data();
+        unsafe {
+            chip::cpDampedRotarySpringSetSpringTorqueFunc(self.get_cp_constraint_mut(),
+                                                          Some(spring_torque_trampoline::<T>));
+        }
+    }
+
+    fn clear_spring_torque_func(&mut self) {
+        self.spring_func = None;
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpSimpleMotor, T> {
+    fn rate(&self) -> Radians {
+        unsafe { Radians(chip::cpSimpleMotorGetRate(self.get_cp_constraint())) }
+    }
+
+    fn set_rate(&mut self, rate: Radians) {
+        unsafe { chip::cpSimpleMotorSetRate(self.get_cp_constraint_mut(), rate.0) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpGearJoint, T> {
+    fn phase(&self) -> Radians {
+        unsafe { Radians(chip::cpGearJointGetPhase(self.get_cp_constraint())) }
+    }
+
+    fn set_phase(&mut self, phase: Radians) {
+        unsafe { chip::cpGearJointSetPhase(self.get_cp_constraint_mut(), phase.0) }
+    }
+
+    fn ratio(&self) -> f64 {
+        unsafe { chip::cpGearJointGetRatio(self.get_cp_constraint()) }
+    }
+
+    fn set_ratio(&mut self, ratio: f64) {
+        unsafe { chip::cpGearJointSetRatio(self.get_cp_constraint_mut(), ratio) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpRatchetJoint, T> {
+    fn angle(&self) -> Radians {
+        unsafe { Radians(chip::cpRatchetJointGetAngle(self.get_cp_constraint())) }
+    }
+
+    fn set_angle(&mut self, angle: Radians) {
+        unsafe { chip::cpRatchetJointSetAngle(self.get_cp_constraint_mut(), angle.0) }
+    }
+
+    fn phase(&self) -> Radians {
+        unsafe { Radians(chip::cpRatchetJointGetPhase(self.get_cp_constraint())) }
+    }
+
+    fn set_phase(&mut self, phase: Radians) {
+        unsafe { chip::cpRatchetJointSetPhase(self.get_cp_constraint_mut(), phase.0) }
+    }
+
+    fn ratchet(&self) -> Radians {
+        unsafe { Radians(chip::cpRatchetJointGetRatchet(self.get_cp_constraint())) }
+    }
+
+    fn set_ratchet(&mut self, ratchet: Radians) {
+        unsafe { chip::cpRatchetJointSetRatchet(self.get_cp_constraint_mut(), ratchet.0) }
+    }
+}
+
+impl <T> ConstraintRaw<chip::cpRotaryLimitJoint, T> {
+    fn min(&self) -> Radians {
+        unsafe { Radians(chip::cpRotaryLimitJointGetMin(self.get_cp_constraint())) }
+    }
+
+    fn set_min(&mut self, min: Radians) {
+        unsafe { chip::cpRotaryLimitJointSetMin(self.get_cp_constraint_mut(), min.0) }
+    }
+
+    fn max(&self) -> Radians {
+        unsafe { Radians(chip::cpRotaryLimitJointGetMax(self.get_cp_constraint())) }
+    }
+
+    fn set_max(&mut self, max: Radians) {
+        unsafe { chip::cpRotaryLimitJointSetMax(self.get_cp_constraint_mut(), max.0) }
+    }
+}
+
+/// Called by chipmunk before the constraint is solved.
+///
+/// The closure is taken out of the raw constraint while it runs, so that
+/// it may safely replace or clear itself.
+extern "C" fn pre_solve_trampoline<C, T>(constraint: *mut chip::cpConstraint, cp_space: *mut chip::cpSpace) {
+    unsafe {
+        let raw = chip::cpConstraintGetUserData(constraint) as *mut ConstraintRaw<C, T>;
+        if let Some(mut func) = (*raw).pre_solve.take() {
+            (*raw).call_solve_func(&mut func, cp_space);
+            if (*raw).pre_solve.is_none() {
+                (*raw).pre_solve = Some(func);
+            }
+        }
+    }
+}
+
+/// Called by chipmunk after the constraint is solved.
+///
+/// See `pre_solve_trampoline`.
+extern "C" fn post_solve_trampoline<C, T>(constraint: *mut chip::cpConstraint, cp_space: *mut chip::cpSpace) {
+    unsafe {
+        let raw = chip::cpConstraintGetUserData(constraint) as *mut ConstraintRaw<C, T>;
+        if let Some(mut func) = (*raw).post_solve.take() {
+            (*raw).call_solve_func(&mut func, cp_space);
+            if (*raw).post_solve.is_none() {
+                (*raw).post_solve = Some(func);
+            }
+        }
+    }
+}
+
+/// Called by chipmunk in place of the default spring force.
+///
+/// Falls back to chipmunk's linear force law when the closure has been
+/// cleared.
+extern "C" fn spring_force_trampoline<T>(constraint: *mut chip::cpConstraint, dist: f64) -> f64 {
+    unsafe {
+        let raw = chip::cpConstraintGetUserData(constraint) as *mut ConstraintRaw<chip::cpDampedSpring, T>;
+        match (*raw).spring_func {
+            Some(ref mut func) => func(dist),
+            None => ((*raw).rest_length() - dist) * (*raw).stiffness()
+        }
+    }
+}
+
+/// Called by chipmunk in place of the default spring torque.
+///
+/// Falls back to chipmunk's linear torque law when the closure has been
+/// cleared.
+extern "C" fn spring_torque_trampoline<T>(constraint: *mut chip::cpConstraint, relative_angle: f64) -> f64 {
+    unsafe {
+        let raw = chip::cpConstraintGetUserData(constraint) as *mut ConstraintRaw<chip::cpDampedRotarySpring, T>;
+        match (*raw).spring_func {
+            Some(ref mut func) => func(relative_angle),
+            None => (relative_angle - (*raw).rest_angle().0) * (*raw).stiffness()
+        }
+    }
+}
+
+impl <C, T> Drop for ConstraintRaw<C, T> {
+    fn drop(&mut self) {
+        unsafe {
+            chip::cpConstraintDestroy(self.get_cp_constraint_mut());
+        }
+    }
+}
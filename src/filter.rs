@@ -0,0 +1,126 @@
+use chip;
+
+/// Shapes in the same non-zero group never collide with each other.
+pub type Group = chip::cpGroup;
+
+/// A set of collision categories, one per bit.
+pub type Bitmask = chip::cpBitmask;
+
+/// A group that no shape is in.
+pub const NO_GROUP: Group = 0;
+
+/// Every collision category.
+pub const ALL_CATEGORIES: Bitmask = !0;
+
+/// Declares named collision categories, assigning one bit to each.
+///
+/// ```ignore
+/// mod layer {
+///     shape_categories!(PLAYER, ENEMY, PLAYER_BULLET, WALL);
+/// }
+///
+/// let bullet = ShapeFilter::all().in_categories(layer::PLAYER_BULLET)
+///                                .ignoring(layer::PLAYER);
+/// ```
+#[macro_export]
+macro_rules! shape_categories {
+    ($($name:ident),+) => {
+        shape_categories!(@bit 0; $($name),+);
+    };
+    ($($name:ident),+,) => {
+        shape_categories!(@bit 0; $($name),+);
+    };
+    (@bit $bit:expr; $name:ident $(, $rest:ident)*) => {
+        #[allow(dead_code)]
+        pub const $name: $crate::filter::Bitmask = 1 << ($bit);
+        shape_categories!(@bit $bit + 1; $($rest),*);
+    };
+    (@bit $bit:expr;) => {};
+}
+
+/// Decides which shapes are allowed to collide with each other.
+///
+/// Two shapes collide unless they are in the same non-zero group, or
+/// either of them has none of the other's categories in its mask.  The
+/// same filter is used by spatial queries to pick the shapes they report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShapeFilter {
+    pub group: Group,
+    pub categories: Bitmask,
+    pub mask: Bitmask
+}
+
+impl ShapeFilter {
+    pub fn new(group: Group, categories: Bitmask, mask: Bitmask) -> ShapeFilter {
+        ShapeFilter {
+            group: group,
+            categories: categories,
+            mask: mask
+        }
+    }
+
+    /// Returns a filter that is in every category and collides with
+    /// everything.
+    ///
+    /// This is the default filter of a shape.
+    pub fn all() -> ShapeFilter {
+        ShapeFilter::new(NO_GROUP, ALL_CATEGORIES, ALL_CATEGORIES)
+    }
+
+    /// Returns a filter that is in no category and collides with nothing.
+    pub fn none() -> ShapeFilter {
+        ShapeFilter::new(NO_GROUP, !ALL_CATEGORIES, !ALL_CATEGORIES)
+    }
+
+    /// Puts the filter in `group`.
+    ///
+    /// Useful for keeping the parts of a ragdoll from colliding with each
+    /// other.
+    pub fn with_group(self, group: Group) -> ShapeFilter {
+        ShapeFilter { group: group, .. self }
+    }
+
+    /// Replaces the categories that the filter is in.
+    pub fn in_categories(self, categories: Bitmask) -> ShapeFilter {
+        ShapeFilter { categories: categories, .. self }
+    }
+
+    /// Replaces the categories that the filter collides with.
+    pub fn colliding_with(self, mask: Bitmask) -> ShapeFilter {
+        ShapeFilter { mask: mask, .. self }
+    }
+
+    /// Stops the filter from colliding with `categories`.
+    pub fn ignoring(self, categories: Bitmask) -> ShapeFilter {
+        ShapeFilter { mask: self.mask & !categories, .. self }
+    }
+
+    /// Returns true if shapes with these two filters can't collide.
+    pub fn rejects(&self, other: &ShapeFilter) -> bool {
+        (self.group != NO_GROUP && self.group == other.group) ||
+        (self.categories & other.mask) == 0 ||
+        (other.categories & self.mask) == 0
+    }
+}
+
+impl Default for ShapeFilter {
+    fn default() -> ShapeFilter {
+        ShapeFilter::all()
+    }
+}
+
+impl From<chip::cpShapeFilter> for ShapeFilter {
+    fn from(filter: chip::cpShapeFilter) -> ShapeFilter {
+        ShapeFilter::new(filter.group, filter.categories, filter.mask)
+    }
+}
+
+impl From<ShapeFilter> for chip::cpShapeFilter {
+    fn from(filter: ShapeFilter) -> chip::cpShapeFilter {
+        chip::cpShapeFilter {
+            group: filter.group,
+            categories: filter.categories,
+            mask: filter.mask
+        }
+    }
+}
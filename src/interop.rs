@@ -0,0 +1,173 @@
+//! Conversions to and from the math types of other crates.
+//!
+//! Each set of conversions is behind the cargo feature of the same name:
+//! `mint`, `glam` and `nalgebra`.  `BB` has no counterpart in these
+//! crates; use `BB::from_corners()`, `BB::min()` and `BB::max()` with the
+//! vector conversions instead.
+
+#[cfg(feature = "mint")]
+mod mint_conv {
+    use mint;
+
+    use super::super::vect::Vect;
+    use super::super::transform::Transform;
+
+    impl From<mint::Vector2<f64>> for Vect {
+        fn from(v: mint::Vector2<f64>) -> Vect {
+            Vect::new(v.x, v.y)
+        }
+    }
+
+    impl From<Vect> for mint::Vector2<f64> {
+        fn from(v: Vect) -> mint::Vector2<f64> {
+            mint::Vector2 { x: v.x, y: v.y }
+        }
+    }
+
+    impl From<mint::Point2<f64>> for Vect {
+        fn from(p: mint::Point2<f64>) -> Vect {
+            Vect::new(p.x, p.y)
+        }
+    }
+
+    impl From<Vect> for mint::Point2<f64> {
+        fn from(v: Vect) -> mint::Point2<f64> {
+            mint::Point2 { x: v.x, y: v.y }
+        }
+    }
+
+    impl From<mint::ColumnMatrix2x3<f64>> for Transform {
+        fn from(m: mint::ColumnMatrix2x3<f64>) -> Transform {
+            Transform::new(m.x.x, m.x.y, m.y.x, m.y.y, m.z.x, m.z.y)
+        }
+    }
+
+    impl From<Transform> for mint::ColumnMatrix2x3<f64> {
+        fn from(t: Transform) -> mint::ColumnMatrix2x3<f64> {
+            mint::ColumnMatrix2x3 {
+                x: mint::Vector2 { x: t.a, y: t.b },
+                y: mint::Vector2 { x: t.c, y: t.d },
+                z: mint::Vector2 { x: t.tx, y: t.ty }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_conv {
+    use glam;
+
+    use super::super::vect::Vect;
+    use super::super::transform::Transform;
+
+    impl From<glam::DVec2> for Vect {
+        fn from(v: glam::DVec2) -> Vect {
+            Vect::new(v.x, v.y)
+        }
+    }
+
+    impl From<Vect> for glam::DVec2 {
+        fn from(v: Vect) -> glam::DVec2 {
+            glam::DVec2::new(v.x, v.y)
+        }
+    }
+
+    impl From<glam::Vec2> for Vect {
+        fn from(v: glam::Vec2) -> Vect {
+            Vect::new(v.x as f64, v.y as f64)
+        }
+    }
+
+    /// Loses precision, as `Vec2` only holds `f32`s.
+    impl From<Vect> for glam::Vec2 {
+        fn from(v: Vect) -> glam::Vec2 {
+            glam::Vec2::new(v.x as f32, v.y as f32)
+        }
+    }
+
+    impl From<glam::DAffine2> for Transform {
+        fn from(t: glam::DAffine2) -> Transform {
+            let c = t.to_cols_array();
+            Transform::new(c[0], c[1], c[2], c[3], c[4], c[5])
+        }
+    }
+
+    impl From<Transform> for glam::DAffine2 {
+        fn from(t: Transform) -> glam::DAffine2 {
+            glam::DAffine2::from_cols_array(&[t.a, t.b, t.c, t.d, t.tx, t.ty])
+        }
+    }
+
+    impl From<Transform> for glam::DMat3 {
+        fn from(t: Transform) -> glam::DMat3 {
+            glam::DMat3::from_cols_array_2d(&t.to_matrix())
+        }
+    }
+
+    /// Loses precision, as `Mat3` only holds `f32`s.
+    impl From<Transform> for glam::Mat3 {
+        fn from(t: Transform) -> glam::Mat3 {
+            glam::DMat3::from(t).as_mat3()
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_conv {
+    use nalgebra as na;
+
+    use super::super::vect::Vect;
+    use super::super::transform::Transform;
+
+    impl From<na::Vector2<f64>> for Vect {
+        fn from(v: na::Vector2<f64>) -> Vect {
+            Vect::new(v.x, v.y)
+        }
+    }
+
+    impl From<Vect> for na::Vector2<f64> {
+        fn from(v: Vect) -> na::Vector2<f64> {
+            na::Vector2::new(v.x, v.y)
+        }
+    }
+
+    impl From<na::Point2<f64>> for Vect {
+        fn from(p: na::Point2<f64>) -> Vect {
+            Vect::new(p.x, p.y)
+        }
+    }
+
+    impl From<Vect> for na::Point2<f64> {
+        fn from(v: Vect) -> na::Point2<f64> {
+            na::Point2::new(v.x, v.y)
+        }
+    }
+
+    /// Ignores the bottom row, which is `[0, 0, 1]` for affine
+    /// transformations.
+    impl From<na::Matrix3<f64>> for Transform {
+        fn from(m: na::Matrix3<f64>) -> Transform {
+            Transform::new(m[(0, 0)], m[(1, 0)], m[(0, 1)], m[(1, 1)], m[(0, 2)], m[(1, 2)])
+        }
+    }
+
+    impl From<Transform> for na::Matrix3<f64> {
+        fn from(t: Transform) -> na::Matrix3<f64> {
+            na::Matrix3::new(t.a, t.c, t.tx,
+                             t.b, t.d, t.ty,
+                             0.0, 0.0, 1.0)
+        }
+    }
+
+    impl From<na::Affine2<f64>> for Transform {
+        fn from(t: na::Affine2<f64>) -> Transform {
+            t.into_inner().into()
+        }
+    }
+
+    impl From<Transform> for na::Affine2<f64> {
+        fn from(t: Transform) -> na::Affine2<f64> {
+            na::Affine2::from_matrix_unchecked(t.into())
+        }
+    }
+}
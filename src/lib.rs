@@ -1,5 +1,11 @@
 extern crate chipmunk_sys as chip;
 extern crate void;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
 
 macro_rules! forward {
     // &self
@@ -51,16 +57,28 @@ macro_rules! forward {
         pub fn $name (&mut self) -> $ret {
             $(#![$doc])*
             unsafe {
-                self.raw.get().$name()
+                (*self.raw.get()).$name()
             }
         }
     };
 }
 
 pub mod util;
+pub mod vect;
+pub mod angle;
+#[macro_use]
+pub mod filter;
 pub mod user_data;
+pub mod bb;
+pub mod transform;
 
 pub mod space;
 pub mod body;
 pub mod shape;
 pub mod arbiter;
+pub mod constraint;
+pub mod collision;
+pub mod query;
+
+mod sensor;
+mod interop;
@@ -0,0 +1,118 @@
+use super::shape::Shape;
+use super::vect::Vect;
+use super::arbiter::ContactPointSet;
+
+use chip;
+
+use void::Void;
+
+/// A shape found by a point query.
+pub struct PointQueryInfo<S=Void> {
+    /// The shape that was found.
+    pub shape: Shape<S>,
+    /// The closest point on the surface of the shape.
+    pub point: Vect,
+    /// The distance to the point.  Negative if the point is inside the
+    /// shape.
+    pub distance: f64,
+    /// The gradient of the signed distance function.
+    ///
+    /// Points away from the surface of the shape, along
+    /// `query_point - point` when the query point is outside.
+    pub gradient: Vect
+}
+
+/// A shape hit by a segment query.
+pub struct SegmentQueryInfo<S=Void> {
+    /// The shape that was hit.
+    pub shape: Shape<S>,
+    /// The point where the segment hit the surface of the shape.
+    pub point: Vect,
+    /// The normal of the surface at the point of impact.
+    pub normal: Vect,
+    /// How far along the segment the hit is, from 0.0 at the start to 1.0
+    /// at the end.
+    pub alpha: f64
+}
+
+/// A shape found by a shape query.
+pub struct ShapeQueryInfo<S=Void> {
+    /// The shape that overlaps the query shape.
+    pub shape: Shape<S>,
+    /// Where the two shapes touch.
+    pub contact_point_set: ContactPointSet
+}
+
+// TODO: hide doc
+/// A hit reported by chipmunk, before it has been matched to a `Shape`.
+pub struct RawPointHit {
+    pub cp_shape: *mut chip::cpShape,
+    pub point: chip::cpVect,
+    pub distance: f64,
+    pub gradient: chip::cpVect
+}
+
+// TODO: hide doc
+/// Collects the hits of `cpSpacePointQuery` into the `Vec<RawPointHit>`
+/// that `data` points at.
+pub extern "C" fn point_query_callback(cp_shape: *mut chip::cpShape, point: chip::cpVect,
+                                       distance: f64, gradient: chip::cpVect,
+                                       data: chip::cpDataPointer) {
+    unsafe {
+        let hits = data as *mut Vec<RawPointHit>;
+        (*hits).push(RawPointHit {
+            cp_shape: cp_shape,
+            point: point,
+            distance: distance,
+            gradient: gradient
+        });
+    }
+}
+
+// TODO: hide doc
+/// A hit reported by chipmunk, before it has been matched to a `Shape`.
+pub struct RawSegmentHit {
+    pub cp_shape: *mut chip::cpShape,
+    pub point: chip::cpVect,
+    pub normal: chip::cpVect,
+    pub alpha: f64
+}
+
+// TODO: hide doc
+/// Collects the hits of `cpSpaceSegmentQuery` into the
+/// `Vec<RawSegmentHit>` that `data` points at.
+pub extern "C" fn segment_query_callback(cp_shape: *mut chip::cpShape, point: chip::cpVect,
+                                         normal: chip::cpVect, alpha: f64,
+                                         data: chip::cpDataPointer) {
+    unsafe {
+        let hits = data as *mut Vec<RawSegmentHit>;
+        (*hits).push(RawSegmentHit {
+            cp_shape: cp_shape,
+            point: point,
+            normal: normal,
+            alpha: alpha
+        });
+    }
+}
+
+// TODO: hide doc
+/// Collects the shapes found by `cpSpaceBBQuery` into the
+/// `Vec<*mut chip::cpShape>` that `data` points at.
+pub extern "C" fn bb_query_callback(cp_shape: *mut chip::cpShape, data: chip::cpDataPointer) {
+    unsafe {
+        let hits = data as *mut Vec<*mut chip::cpShape>;
+        (*hits).push(cp_shape);
+    }
+}
+
+// TODO: hide doc
+/// Collects the hits of `cpSpaceShapeQuery` into the
+/// `Vec<(*mut chip::cpShape, ContactPointSet)>` that `data` points at.
+pub extern "C" fn shape_query_callback(cp_shape: *mut chip::cpShape,
+                                       points: *mut chip::cpContactPointSet,
+                                       data: chip::cpDataPointer) {
+    unsafe {
+        let hits = data as *mut Vec<(*mut chip::cpShape, ContactPointSet)>;
+        (*hits).push((cp_shape, (*points).into()));
+    }
+}
@@ -0,0 +1,132 @@
+use super::shape::Shape;
+
+use chip;
+
+/// Keeps track of the shapes that overlap each tracked sensor.
+///
+/// The space feeds it from the begin and separate callbacks of the
+/// wildcard handlers that were set up with `Space::track_sensors()`.
+pub struct SensorTracker {
+    overlaps: Vec<(Shape, Vec<Shape>)>,
+    entered: Vec<(Shape, Shape)>,
+    exited: Vec<(Shape, Shape)>,
+    // Changes made between steps, reported along with the next step.
+    pending_entered: Vec<(Shape, Shape)>,
+    pending_exited: Vec<(Shape, Shape)>,
+    in_step: bool,
+}
+
+impl SensorTracker {
+    pub fn new() -> SensorTracker {
+        SensorTracker {
+            overlaps: Vec::new(),
+            entered: Vec::new(),
+            exited: Vec::new(),
+            pending_entered: Vec::new(),
+            pending_exited: Vec::new(),
+            in_step: false,
+        }
+    }
+
+    /// Forgets the changes reported for the previous step.
+    ///
+    /// Changes that were made since then, such as shapes removed between
+    /// steps, are kept and reported with this step.
+    pub fn begin_step(&mut self) {
+        self.entered.clear();
+        self.exited.clear();
+        self.entered.append(&mut self.pending_entered);
+        self.exited.append(&mut self.pending_exited);
+        self.in_step = true;
+    }
+
+    pub fn end_step(&mut self) {
+        self.in_step = false;
+    }
+
+    pub fn enter(&mut self, sensor: Shape, other: Shape) {
+        unsafe {
+            let pos = self.overlaps.iter().position(|e| e.0.get_cp_shape() == sensor.get_cp_shape());
+            let pos = match pos {
+                Some(pos) => pos,
+                None => {
+                    self.overlaps.push((sensor.duplicate(), Vec::new()));
+                    self.overlaps.len() - 1
+                }
+            };
+            let others = &mut self.overlaps[pos].1;
+            if others.iter().any(|e| e.get_cp_shape() == other.get_cp_shape()) {
+                return;
+            }
+            others.push(other.duplicate());
+        }
+        if self.in_step {
+            self.entered.push((sensor, other));
+        } else {
+            self.pending_entered.push((sensor, other));
+        }
+    }
+
+    pub fn exit(&mut self, sensor: Shape, other: Shape) {
+        unsafe {
+            let pos = self.overlaps.iter().position(|e| e.0.get_cp_shape() == sensor.get_cp_shape());
+            let pos = match pos {
+                Some(pos) => pos,
+                None => return
+            };
+            let found = {
+                let others = &mut self.overlaps[pos].1;
+                match others.iter().position(|e| e.get_cp_shape() == other.get_cp_shape()) {
+                    Some(i) => { others.remove(i); true }
+                    None => false
+                }
+            };
+            if self.overlaps[pos].1.is_empty() {
+                self.overlaps.remove(pos);
+            }
+            if !found {
+                return;
+            }
+        }
+        if self.in_step {
+            self.exited.push((sensor, other));
+        } else {
+            self.pending_exited.push((sensor, other));
+        }
+    }
+
+    /// Drops every overlap that involves `shape`.
+    ///
+    /// Called when a shape leaves the space so that no stale handles are
+    /// kept around.
+    pub fn forget(&mut self, shape: *const chip::cpShape) {
+        unsafe {
+            self.overlaps.retain(|e| e.0.get_cp_shape() != shape);
+            for &mut (_, ref mut others) in self.overlaps.iter_mut() {
+                others.retain(|e| e.get_cp_shape() != shape);
+            }
+            self.overlaps.retain(|e| !e.1.is_empty());
+        }
+    }
+
+    pub fn overlaps(&self, sensor: *const chip::cpShape) -> Vec<Shape> {
+        unsafe {
+            match self.overlaps.iter().find(|e| e.0.get_cp_shape() == sensor) {
+                Some(&(_, ref others)) => others.iter().map(|e| e.duplicate()).collect(),
+                None => Vec::new()
+            }
+        }
+    }
+
+    pub fn entered(&self) -> Vec<(Shape, Shape)> {
+        unsafe {
+            self.entered.iter().map(|e| (e.0.duplicate(), e.1.duplicate())).collect()
+        }
+    }
+
+    pub fn exited(&self) -> Vec<(Shape, Shape)> {
+        unsafe {
+            self.exited.iter().map(|e| (e.0.duplicate(), e.1.duplicate())).collect()
+        }
+    }
+}
@@ -6,6 +6,13 @@ use std::marker::PhantomData;
 
 use super::user_data::UserData;
 use super::body::Body;
+use super::collision::CollisionType;
+use super::filter::ShapeFilter;
+use super::arbiter::ContactPointSet;
+use super::query::{PointQueryInfo, SegmentQueryInfo};
+use super::vect::Vect;
+use super::bb::BB;
+use super::transform::Transform;
 
 use chip;
 
@@ -32,21 +39,21 @@ pub struct SegmentShape<T=Void> {
 struct PolyShapeRaw<T=Void> {
     cp_shape: chip::cpPolyShape,
     user_data: Option<Box<Any>>,
-    _attached_body: Body<Void>,
+    attached_body: Body<Void>,
     _phantom: PhantomData<T>
 }
 
 struct CircleShapeRaw<T=Void> {
     cp_shape: chip::cpCircleShape,
     user_data: Option<Box<Any>>,
-    _attached_body: Body<Void>,
+    attached_body: Body<Void>,
     _phantom: PhantomData<T>
 }
 
 struct SegmentShapeRaw<T=Void> {
     cp_shape: chip::cpSegmentShape,
     user_data: Option<Box<Any>>,
-    _attached_body: Body<Void>,
+    attached_body: Body<Void>,
     _phantom: PhantomData<T>
 }
 
@@ -73,60 +80,92 @@ impl <T: 'static + Any> UserData<T> for Shape<T> {
 }
 
 impl Shape<Void> {
-    pub fn new_segment(body: &mut Body, start: (f64, f64), end: (f64, f64), radius: f64) -> Shape<Void> {
+    pub fn new_segment<P: Into<Vect>, Q: Into<Vect>>(body: &mut Body, start: P, end: Q,
+                                                     radius: f64) -> Shape<Void> {
         let mut shape = SegmentShapeRaw {
             cp_shape: unsafe { zeroed() },
             user_data: None,
-            _attached_body: unsafe { body.duplicate() },
+            attached_body: unsafe { body.duplicate() },
             _phantom: PhantomData
         };
-        let a = chip::cpv(start.0, start.1);
-        let b = chip::cpv(end.0, end.1);
+        let (start, end): (Vect, Vect) = (start.into(), end.into());
         unsafe {
-            chip::cpSegmentShapeInit(&mut shape.cp_shape, body.get_cp_body(), a, b, radius);
+            chip::cpSegmentShapeInit(&mut shape.cp_shape, body.get_cp_body(),
+                                     start.into(), end.into(), radius);
         }
 
         Shape::Segment(SegmentShape{ raw: Rc::new(UnsafeCell::new(shape)) })
     }
 
-    pub fn new_circle(body: &mut Body, radius: f64, offset: (f64, f64)) -> Shape<Void> {
+    pub fn new_circle<P: Into<Vect>>(body: &mut Body, radius: f64, offset: P) -> Shape<Void> {
         let mut shape = CircleShapeRaw {
             cp_shape: unsafe { zeroed() },
             user_data: None,
-            _attached_body: unsafe { body.duplicate() },
+            attached_body: unsafe { body.duplicate() },
             _phantom: PhantomData
         };
 
-        let offset = chip::cpv(offset.0, offset.1);
+        let offset: Vect = offset.into();
         unsafe {
-            chip::cpCircleShapeInit(&mut shape.cp_shape, body.get_cp_body(), radius, offset);
+            chip::cpCircleShapeInit(&mut shape.cp_shape, body.get_cp_body(), radius, offset.into());
         }
 
         Shape::Circle(CircleShape{ raw: Rc::new(UnsafeCell::new(shape)) })
     }
 
-    pub fn new_poly(body: &mut Body, points: &[(f64)], radius: f64) -> Shape<Void> {
+    pub fn new_poly<P: Into<Vect> + Copy>(body: &mut Body, points: &[P], radius: f64) -> Shape<Void> {
         let mut shape = PolyShapeRaw {
             cp_shape: unsafe { zeroed() },
             user_data: None,
-            _attached_body: unsafe { body.duplicate() },
+            attached_body: unsafe { body.duplicate() },
             _phantom: PhantomData
         };
 
+        let verts: Vec<chip::cpVect> = points.iter().map(|&p| {
+            let p: Vect = p.into();
+            p.into()
+        }).collect();
         unsafe {
             chip::cpPolyShapeInitRaw(&mut shape.cp_shape, body.get_cp_body(),
-                                    points.len() as i32, transmute(points.as_ptr()),
+                                    verts.len() as i32, verts.as_ptr(),
                                     radius);
         }
 
         Shape::Poly(PolyShape{ raw: Rc::new(UnsafeCell::new(shape)) })
     }
 
+    /// Creates a polygon from the convex hull of `points` after they have
+    /// been moved by `transform`.
+    ///
+    /// `radius` rounds the corners of the polygon.
+    pub fn new_poly_transformed<P: Into<Vect> + Copy>(body: &mut Body, points: &[P],
+                                                     transform: Transform,
+                                                     radius: f64) -> Shape<Void> {
+        let mut shape = PolyShapeRaw {
+            cp_shape: unsafe { zeroed() },
+            user_data: None,
+            attached_body: unsafe { body.duplicate() },
+            _phantom: PhantomData
+        };
+
+        let verts: Vec<chip::cpVect> = points.iter().map(|&p| {
+            let p: Vect = p.into();
+            p.into()
+        }).collect();
+        unsafe {
+            chip::cpPolyShapeInit(&mut shape.cp_shape, body.get_cp_body(),
+                                  verts.len() as i32, verts.as_ptr(),
+                                  transform.into(), radius);
+        }
+
+        Shape::Poly(PolyShape{ raw: Rc::new(UnsafeCell::new(shape)) })
+    }
+
     pub fn new_box(body: &mut Body, width: f64, height: f64, radius: f64) -> Shape<Void> {
         let mut shape = PolyShapeRaw {
             cp_shape: unsafe { zeroed() },
             user_data: None,
-            _attached_body: unsafe { body.duplicate() },
+            attached_body: unsafe { body.duplicate() },
             _phantom: PhantomData
         };
 
@@ -152,6 +191,17 @@ impl <T> Shape<T> {
         transmute(self.get_cp_shape())
     }
 
+    /// Returns the body that this shape is attached to.
+    pub fn body(&self) -> Body<Void> {
+        unsafe {
+            match *self {
+                Shape::Poly(ref p) => (*p.raw.get()).attached_body.duplicate(),
+                Shape::Circle(ref p) => (*p.raw.get()).attached_body.duplicate(),
+                Shape::Segment(ref p) => (*p.raw.get()).attached_body.duplicate(),
+            }
+        }
+    }
+
     pub unsafe fn duplicate(&self) -> Shape<Void> {
         match *self {
             Shape::Poly(ref p) => Shape::Poly(transmute(PolyShape{raw: p.raw.clone()})),
@@ -160,6 +210,59 @@ impl <T> Shape<T> {
         }
     }
 
+    /// Returns the collision type of this shape.
+    ///
+    /// Defaults to 0.
+    pub fn collision_type(&self) -> CollisionType {
+        unsafe {
+            chip::cpShapeGetCollisionType(self.get_cp_shape())
+        }
+    }
+
+    /// Returns the bounding box of this shape.
+    ///
+    /// The box is only refreshed when the space steps or when
+    /// `cache_bb()` or `update()` is called.
+    pub fn bb(&self) -> BB {
+        unsafe {
+            chip::cpShapeGetBB(self.get_cp_shape()).into()
+        }
+    }
+
+    /// Recomputes the bounding box of this shape from the position of its
+    /// body and returns it.
+    pub fn cache_bb(&mut self) -> BB {
+        unsafe {
+            chip::cpShapeCacheBB(self.get_cp_shape_mut()).into()
+        }
+    }
+
+    /// Recomputes the bounding box of this shape as if its body was placed
+    /// by `transform`, and returns it.
+    ///
+    /// This also updates the cached collision data of the shape, so it is
+    /// mostly useful for shapes that aren't part of a space.
+    pub fn update(&mut self, transform: Transform) -> BB {
+        unsafe {
+            chip::cpShapeUpdate(self.get_cp_shape_mut(), transform.into()).into()
+        }
+    }
+
+    /// Returns the area of this shape.
+    pub fn area(&self) -> f64 {
+        unsafe {
+            chip::cpShapeGetArea(self.get_cp_shape() as *mut chip::cpShape)
+        }
+    }
+
+    /// Returns the center of gravity of this shape in the local
+    /// coordinates of its body.
+    pub fn center_of_gravity(&self) -> Vect {
+        unsafe {
+            chip::cpShapeGetCenterOfGravity(self.get_cp_shape() as *mut chip::cpShape).into()
+        }
+    }
+
     pub fn density(&self) -> f64 {
         unsafe {
             chip::cpShapeGetDensity(self.get_cp_shape())
@@ -172,6 +275,15 @@ impl <T> Shape<T> {
         }
     }
 
+    /// Returns the collision filter of this shape.
+    ///
+    /// Defaults to `ShapeFilter::all()`.
+    pub fn filter(&self) -> ShapeFilter {
+        unsafe {
+            chip::cpShapeGetFilter(self.get_cp_shape()).into()
+        }
+    }
+
     pub fn friction(&self) -> f64 {
         unsafe {
             chip::cpShapeGetFriction(self.get_cp_shape())
@@ -184,6 +296,15 @@ impl <T> Shape<T> {
         }
     }
 
+    /// Returns the moment of inertia that this shape adds to its body.
+    ///
+    /// 0.0 unless the shape was given a mass or density.
+    pub fn moment(&self) -> f64 {
+        unsafe {
+            chip::cpShapeGetMoment(self.get_cp_shape() as *mut chip::cpShape)
+        }
+    }
+
     /// Returns true if this shape is a sensor.
     ///
     /// A sensor is a shape that dosn't participate in collisions, but
@@ -196,13 +317,65 @@ impl <T> Shape<T> {
     }
 
     /// Returns the velocity of the shape at the surface.
-    pub fn surface_velocity(&self) -> (f64, f64) {
+    pub fn surface_velocity(&self) -> Vect {
+        unsafe {
+            chip::cpShapeGetSurfaceVelocity(self.get_cp_shape()).into()
+        }
+    }
+
+    /// Returns the point on this shape that is closest to `point`.
+    ///
+    /// The `shape` of the result is this shape.  The shape doesn't need to
+    /// be part of a space; its position is taken from its body, which also
+    /// updates its cached bounding box.
+    pub fn point_query<P: Into<Vect>>(&mut self, point: P) -> PointQueryInfo<T> {
+        let point: Vect = point.into();
         unsafe {
-            let v = chip::cpShapeGetSurfaceVelocity(self.get_cp_shape());
-            (v.x, v.y)
+            let mut info: chip::cpPointQueryInfo = zeroed();
+            chip::cpShapeCacheBB(self.get_cp_shape_mut());
+            chip::cpShapePointQuery(self.get_cp_shape(), point.into(), &mut info);
+            PointQueryInfo {
+                shape: transmute(self.duplicate()),
+                point: info.point.into(),
+                distance: info.distance,
+                gradient: info.gradient.into()
+            }
         }
     }
 
+    /// Returns where the segment from `start` to `end` hits this shape, if
+    /// it does.
+    ///
+    /// `radius` makes the segment thicker.  The shape doesn't need to be
+    /// part of a space; its position is taken from its body, which also
+    /// updates its cached bounding box.
+    pub fn segment_query<P: Into<Vect>, Q: Into<Vect>>(&mut self, start: P, end: Q,
+                                                       radius: f64) -> Option<SegmentQueryInfo<T>> {
+        let (start, end): (Vect, Vect) = (start.into(), end.into());
+        unsafe {
+            let mut info: chip::cpSegmentQueryInfo = zeroed();
+            chip::cpShapeCacheBB(self.get_cp_shape_mut());
+            let hit = chip::cpShapeSegmentQuery(self.get_cp_shape(),
+                                                start.into(),
+                                                end.into(),
+                                                radius, &mut info);
+            if hit == 0 {
+                return None;
+            }
+            Some(SegmentQueryInfo {
+                shape: transmute(self.duplicate()),
+                point: info.point.into(),
+                normal: info.normal.into(),
+                alpha: info.alpha
+            })
+        }
+    }
+
+    /// Sets the density of this shape, which derives its mass from its
+    /// area.
+    ///
+    /// The mass properties of the body are recomputed from its shapes, see
+    /// `Body::new_dynamic()`.
     pub fn set_density(&mut self, density: f64) {
         unsafe {
             chip::cpShapeSetDensity(self.get_cp_shape_mut(), density);
@@ -215,8 +388,24 @@ impl <T> Shape<T> {
         }
     }
 
-    // set collision type
-    // set filter
+    /// Sets the collision type of this shape.
+    ///
+    /// The collision types of two touching shapes pick the collision
+    /// handler that the space calls.
+    pub fn set_collision_type(&mut self, collision_type: CollisionType) {
+        unsafe {
+            chip::cpShapeSetCollisionType(self.get_cp_shape_mut(), collision_type);
+        }
+    }
+
+    /// Sets the collision filter of this shape.
+    ///
+    /// See `ShapeFilter` for how filters decide which shapes collide.
+    pub fn set_filter(&mut self, filter: ShapeFilter) {
+        unsafe {
+            chip::cpShapeSetFilter(self.get_cp_shape_mut(), filter.into());
+        }
+    }
 
     pub fn set_friction(&mut self, friction: f64) {
         unsafe {
@@ -224,6 +413,10 @@ impl <T> Shape<T> {
         }
     }
 
+    /// Sets the mass of this shape.
+    ///
+    /// The mass properties of the body are recomputed from its shapes, see
+    /// `Body::new_dynamic()`.
     pub fn set_mass(&mut self, mass: f64) {
         unsafe {
             chip::cpShapeSetMass(self.get_cp_shape_mut(), mass);
@@ -238,14 +431,27 @@ impl <T> Shape<T> {
     }
 
 
-    pub fn set_surface_velocity(&mut self, surface_velocity: (f64, f64)) {
+    pub fn set_surface_velocity<P: Into<Vect>>(&mut self, surface_velocity: P) {
+        let surface_velocity: Vect = surface_velocity.into();
         unsafe {
-            let cpv = chip::cpv(surface_velocity.0, surface_velocity.1);
-            chip::cpShapeSetSurfaceVelocity(self.get_cp_shape_mut(), cpv);
+            chip::cpShapeSetSurfaceVelocity(self.get_cp_shape_mut(), surface_velocity.into());
         }
     }
 }
 
+/// Returns where `a` and `b` touch.
+///
+/// The shapes don't need to be part of a space; their positions are taken
+/// from their bodies, which also updates their cached bounding boxes.  The
+/// `count` of the result is 0 if they don't touch.
+pub fn shapes_collide<A, B>(a: &mut Shape<A>, b: &mut Shape<B>) -> ContactPointSet {
+    unsafe {
+        chip::cpShapeCacheBB(a.get_cp_shape_mut());
+        chip::cpShapeCacheBB(b.get_cp_shape_mut());
+        chip::cpShapesCollide(a.get_cp_shape(), b.get_cp_shape()).into()
+    }
+}
+
 impl <T> PolyShape<T> {
     forward!(count(&self) -> usize,
     /// Returns the number of vertices in this shape.
@@ -255,13 +461,13 @@ impl <T> PolyShape<T> {
     /// Returns the radius that encompases all the vertices.
     );
 
-    forward!(vert(&self, i: usize) -> (f64, f64),
+    forward!(vert(&self, i: usize) -> Vect,
     /// Returns the i-th vertex in this shape.
     );
 }
 
 impl <T> CircleShape<T> {
-    forward!(offset(&self) -> (f64, f64),
+    forward!(offset(&self) -> Vect,
     /// Returns the local offset at which the shape is
     /// placed relative to the body that it is attached to.
     );
@@ -272,15 +478,15 @@ impl <T> CircleShape<T> {
 }
 
 impl <T> SegmentShape<T> {
-    forward!(start(&self) -> (f64, f64),
+    forward!(start(&self) -> Vect,
     /// Returns the first point in the segment.
     );
 
-    forward!(end(&self) -> (f64, f64),
+    forward!(end(&self) -> Vect,
     /// Returns the second poin in the segment.
     );
 
-    forward!(normal(&self) -> (f64, f64),
+    forward!(normal(&self) -> Vect,
     /// Returns the normal vector given by this segment.
     );
 
@@ -302,20 +508,18 @@ impl <T> PolyShapeRaw<T> {
         }
     }
 
-    fn vert(&self, index: usize)  -> (f64, f64) {
+    fn vert(&self, index: usize)  -> Vect {
         unsafe {
             let index = index as i32;
-            let cpv = chip::cpPolyShapeGetVert(transmute(&self.cp_shape), index);
-            (cpv.x, cpv.y)
+            chip::cpPolyShapeGetVert(transmute(&self.cp_shape), index).into()
         }
     }
 }
 
 impl <T> CircleShapeRaw<T> {
-    fn offset(&self) -> (f64, f64) {
+    fn offset(&self) -> Vect {
         unsafe {
-            let cpv = chip::cpCircleShapeGetOffset(transmute(&self.cp_shape));
-            (cpv.x, cpv.y)
+            chip::cpCircleShapeGetOffset(transmute(&self.cp_shape)).into()
         }
     }
 
@@ -327,24 +531,21 @@ impl <T> CircleShapeRaw<T> {
 }
 
 impl <T> SegmentShapeRaw<T> {
-    fn start(&self) -> (f64, f64) {
+    fn start(&self) -> Vect {
         unsafe {
-            let cpv = chip::cpSegmentShapeGetA(transmute(&self.cp_shape));
-            (cpv.x, cpv.y)
+            chip::cpSegmentShapeGetA(transmute(&self.cp_shape)).into()
         }
     }
 
-    fn end(&self) -> (f64, f64) {
+    fn end(&self) -> Vect {
         unsafe {
-            let cpv = chip::cpSegmentShapeGetB(transmute(&self.cp_shape));
-            (cpv.x, cpv.y)
+            chip::cpSegmentShapeGetB(transmute(&self.cp_shape)).into()
         }
     }
 
-    fn normal(&self) -> (f64, f64) {
+    fn normal(&self) -> Vect {
         unsafe {
-            let cpv = chip::cpSegmentShapeGetNormal(transmute(&self.cp_shape));
-            (cpv.x, cpv.y)
+            chip::cpSegmentShapeGetNormal(transmute(&self.cp_shape)).into()
         }
     }
 
@@ -1,8 +1,11 @@
 use std::any::Any;
 use std::rc::Rc;
-use std::cell::UnsafeCell;
+use std::cell::{UnsafeCell, RefCell};
+use std::collections::HashMap;
 use std::mem;
 use std::marker::PhantomData;
+use std::vec;
+use std::cmp::Ordering;
 
 use chip;
 use void::Void;
@@ -10,13 +13,41 @@ use void::Void;
 use super::user_data::UserData;
 use super::body::Body;
 use super::shape::Shape;
+use super::constraint::Constraint;
+use super::collision::{CollisionHandler, CollisionType, CollisionEvent};
+use super::sensor::SensorTracker;
+use super::filter::ShapeFilter;
+use super::query::{self, PointQueryInfo, RawPointHit, SegmentQueryInfo, RawSegmentHit};
+use super::query::ShapeQueryInfo;
+use super::arbiter::ContactPointSet;
+use super::bb::BB;
+use super::vect::Vect;
 
 
 struct SpaceRaw<T=Void> {
     cp_space: chip::cpSpace,
     user_data: Option<Box<Any>>,
     bodies: Vec<Body<Void>>,
-    shapes: Vec<Shape<Void>>,
+    /// Keyed by the chipmunk shape, so that callbacks can find the
+    /// handles of the shapes that they are passed.
+    shapes: HashMap<*const chip::cpShape, Shape<Void>>,
+    /// Keyed by the chipmunk constraint, for the same reason as `shapes`.
+    constraints: HashMap<*const chip::cpConstraint, Constraint<Void>>,
+    broken_constraints: Vec<Constraint<Void>>,
+    collision_handlers: Vec<CollisionHandler<T>>,
+    collision_events: Vec<CollisionEvent>,
+    sensor_tracker: SensorTracker,
+    /// The direction of every one-way platform, shared with the pre-solve
+    /// closures that check them.
+    one_way_platforms: Rc<RefCell<HashMap<*const chip::cpShape, Vect>>>,
+    /// The collision types whose wildcard handler checks for platforms.
+    one_way_types: Vec<CollisionType>,
+    /// The user data set on arbiters, which only live as long as their
+    /// collision does.
+    arbiter_user_data: HashMap<*const chip::cpArbiter, Option<Box<Any>>>,
+    /// Arbiters whose collision ended, whose user data is dropped once the
+    /// callbacks have returned.
+    separated_arbiters: Vec<*const chip::cpArbiter>,
     _phantom: PhantomData<T>,
 }
 
@@ -26,12 +57,88 @@ pub struct Space<T=Void> {
 
 impl Space<Void> {
     pub fn new() -> Space<Void> {
-        Space {
+        let space = Space {
             raw: Rc::new(UnsafeCell::new(SpaceRaw::new()))
+        };
+        unsafe {
+            // Callbacks find their way back to the space through this.
+            let raw = space.raw.get();
+            chip::cpSpaceSetUserData(&mut (*raw).cp_space, raw as chip::cpDataPointer);
         }
+        space
     }
 }
 
+// TODO: hide doc
+/// Returns a handle to the constraint in `cp_space` that wraps
+/// `cp_constraint`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn find_constraint(cp_space: *mut chip::cpSpace,
+                              cp_constraint: *const chip::cpConstraint) -> Option<Constraint<Void>> {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).constraints.get(&cp_constraint).map(|c| c.duplicate())
+}
+
+// TODO: hide doc
+/// Returns a handle to the shape in `cp_space` that wraps `cp_shape`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn find_shape(cp_space: *mut chip::cpSpace,
+                         cp_shape: *const chip::cpShape) -> Option<Shape<Void>> {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).find_shape(cp_shape)
+}
+
+// TODO: hide doc
+/// Adds an event to the queue of the space that owns `cp_space`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn push_collision_event(cp_space: *mut chip::cpSpace, event: CollisionEvent) {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).collision_events.push(event);
+}
+
+// TODO: hide doc
+/// Returns the user data slot of `cp_arbiter` in the space that owns
+/// `cp_space`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn arbiter_user_data<'a>(cp_space: *mut chip::cpSpace,
+                                    cp_arbiter: *const chip::cpArbiter) -> &'a mut Option<Box<Any>> {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).arbiter_user_data.entry(cp_arbiter).or_insert(None)
+}
+
+// TODO: hide doc
+/// Drops the user data of `cp_arbiter` once the step or removal that ended
+/// its collision is done.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn separate_arbiter(cp_space: *mut chip::cpSpace, cp_arbiter: *const chip::cpArbiter) {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).separated_arbiters.push(cp_arbiter);
+}
+
+// TODO: hide doc
+/// Returns the sensor tracker of the space that owns `cp_space`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn sensor_tracker<'a>(cp_space: *mut chip::cpSpace) -> &'a mut SensorTracker {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    &mut (*raw).sensor_tracker
+}
+
+// TODO: hide doc
+/// Returns the user data of the space that owns `cp_space` if it is of
+/// type `T`.
+///
+/// `cp_space` must belong to a `Space` created by this crate.
+pub unsafe fn find_user_data<'a, T: 'static + Any>(cp_space: *mut chip::cpSpace) -> Option<&'a mut T> {
+    let raw = chip::cpSpaceGetUserData(cp_space) as *mut SpaceRaw<Void>;
+    (*raw).user_data.as_mut().and_then(|a| a.downcast_mut())
+}
+
 impl <T> Space<T> {
     pub fn duplicate_homogenous(&mut self) -> Space<Void> {
         use std::mem::transmute;
@@ -67,6 +174,12 @@ impl <T> Space<T> {
         }
     }
 
+    pub fn add_constraint<A>(&mut self, constraint: &mut Constraint<A>){
+        unsafe {
+            (*self.raw.get()).add_constraint(constraint);
+        }
+    }
+
     pub fn remove_body<A>(&mut self, body: Body<A>){
         unsafe {
             (*self.raw.get()).remove_body(body);
@@ -79,17 +192,132 @@ impl <T> Space<T> {
         }
     }
 
+    pub fn remove_constraint<A>(&mut self, constraint: Constraint<A>){
+        unsafe {
+            (*self.raw.get()).remove_constraint(constraint);
+        }
+    }
+
+    /// Returns the shapes that currently overlap `sensor`.
+    ///
+    /// Only sensors whose collision type is tracked are reported, see
+    /// `track_sensors()`.
+    pub fn sensor_overlaps<A>(&self, sensor: &Shape<A>) -> Vec<Shape> {
+        unsafe {
+            (*self.raw.get()).sensor_tracker.overlaps(sensor.get_cp_shape())
+        }
+    }
+
+    forward!(sensor_entered(&self) -> Vec<(Shape, Shape)>,
+    /// Returns the `(sensor, shape)` pairs that started overlapping during
+    /// the last step.
+    );
+
+    forward!(sensor_exited(&self) -> Vec<(Shape, Shape)>,
+    /// Returns the `(sensor, shape)` pairs that stopped overlapping during
+    /// the last step.
+    ///
+    /// Shapes removed from the space during a step are reported with that
+    /// step, shapes removed between steps are reported after the next one.
+    );
+
+    /// Returns the shape closest to `point`, if there is one within
+    /// `max_distance`.
+    ///
+    /// Only shapes that `filter` doesn't reject are considered.  A
+    /// `max_distance` of 0.0 only finds shapes that contain the point.
+    /// `S` is the type of user data attached to the shapes.
+    pub fn point_query_nearest<S, P: Into<Vect>>(&self, point: P, max_distance: f64,
+                                                 filter: ShapeFilter) -> Option<PointQueryInfo<S>> {
+        unsafe {
+            (*self.raw.get()).point_query_nearest(point.into(), max_distance, filter)
+        }
+    }
+
+    /// Returns every shape within `max_distance` of `point`.
+    ///
+    /// Only shapes that `filter` doesn't reject are considered.
+    /// `S` is the type of user data attached to the shapes.
+    pub fn point_query<S, P: Into<Vect>>(&self, point: P, max_distance: f64,
+                                         filter: ShapeFilter) -> vec::IntoIter<PointQueryInfo<S>> {
+        unsafe {
+            (*self.raw.get()).point_query(point.into(), max_distance, filter)
+        }
+    }
+
+    /// Returns the first shape hit by the segment from `start` to `end`.
+    ///
+    /// `radius` makes the segment thicker, which is useful for sweeping
+    /// round objects.  Only shapes that `filter` doesn't reject are
+    /// considered.  `S` is the type of user data attached to the shapes.
+    pub fn segment_query_first<S, P: Into<Vect>, Q: Into<Vect>>(&self, start: P, end: Q, radius: f64,
+                                                                filter: ShapeFilter) -> Option<SegmentQueryInfo<S>> {
+        unsafe {
+            (*self.raw.get()).segment_query_first(start.into(), end.into(), radius, filter)
+        }
+    }
+
+    /// Returns every shape hit by the segment from `start` to `end`,
+    /// ordered by how far along the segment they were hit.
+    ///
+    /// See `segment_query_first()`.
+    pub fn segment_query<S, P: Into<Vect>, Q: Into<Vect>>(&self, start: P, end: Q, radius: f64,
+                                                          filter: ShapeFilter) -> vec::IntoIter<SegmentQueryInfo<S>> {
+        unsafe {
+            (*self.raw.get()).segment_query(start.into(), end.into(), radius, filter)
+        }
+    }
+
+    /// Returns every shape whose bounding box overlaps `bb`.
+    ///
+    /// Only shapes that `filter` doesn't reject are considered.
+    /// `S` is the type of user data attached to the shapes.
+    pub fn bb_query<S>(&self, bb: BB, filter: ShapeFilter) -> vec::IntoIter<Shape<S>> {
+        unsafe {
+            (*self.raw.get()).bb_query(bb, filter)
+        }
+    }
+
+    /// Returns every shape in the space that `shape` would collide with.
+    ///
+    /// `shape` doesn't need to be part of the space, which makes this
+    /// useful for checking that a spot is free before placing something
+    /// there.  The filter of `shape` picks the shapes that are considered.
+    pub fn shape_query<S>(&self, shape: &Shape<S>) -> vec::IntoIter<ShapeQueryInfo<T>> {
+        unsafe {
+            (*self.raw.get()).shape_query(shape)
+        }
+    }
+
     forward!(step(&mut self, timestep: f64) -> (),
     /// Moves the simulation forward by one tick.
     ///
     /// `timestep` is the amount of time ellapsed in the simulation since
     /// the last time `step()` was called.
+    ///
+    /// Breakable constraints that were overloaded during the step are
+    /// removed from the space afterwards.
+    );
+
+    forward!(drain_broken_constraints(&mut self) -> Vec<Constraint<Void>>,
+    /// Returns every constraint that broke since the last call to this
+    /// method.
+    ///
+    /// Broken constraints are no longer part of the space, but can be
+    /// added back with `add_constraint()`.
     );
 
-    forward!(gravity(&self) -> (f64, f64),
+    forward!(drain_collision_events(&mut self) -> Vec<CollisionEvent>,
+    /// Returns every collision event recorded since the last call to this
+    /// method.
+    ///
+    /// See `record_collision_events()`.
+    );
+
+    forward!(gravity(&self) -> Vect,
     /// Returns the global gravity for all rigid bodies in this space.
     ///
-    /// Default is `(0.0, 0.0)`.
+    /// Default is `Vect::zero()`.
     );
 
     forward!(damping(&self) -> f64,
@@ -147,11 +375,14 @@ impl <T> Space<T> {
     );
 
 
-    forward!(set_gravity(&mut self, ax: f64, ay: f64) -> (),
     /// Sets the global gravity for all rigid bodies in this space.
     ///
     /// Default is `<0, 0>` (no gravity).
-    );
+    pub fn set_gravity<P: Into<Vect>>(&mut self, gravity: P) {
+        unsafe {
+            (*self.raw.get()).set_gravity(gravity.into())
+        }
+    }
 
     forward!(set_damping(&mut self, damping: f64) -> (),
     /// Sets the global velocity damping.
@@ -202,6 +433,99 @@ impl <T> Space<T> {
 
 }
 
+impl <T: 'static + Any> Space<T> {
+    /// Returns the collision handler that is called when shapes of
+    /// `type_a` and `type_b` touch.
+    ///
+    /// Calling this more than once for the same pair of types returns
+    /// the same handler.
+    pub fn add_collision_handler(&mut self, type_a: CollisionType,
+                                 type_b: CollisionType) -> CollisionHandler<T> {
+        unsafe {
+            (*self.raw.get()).add_collision_handler(type_a, type_b)
+        }
+    }
+
+    /// Returns the collision handler that is called when a shape of
+    /// `collision_type` touches a shape of any type.
+    ///
+    /// Wildcard handlers are only called when no pair handler matches, or
+    /// when a pair handler calls them through the arbiter (see
+    /// `Arbiter::call_wildcard_begin()` and friends).
+    pub fn add_wildcard_handler(&mut self, collision_type: CollisionType) -> CollisionHandler<T> {
+        unsafe {
+            (*self.raw.get()).add_wildcard_handler(collision_type)
+        }
+    }
+
+    /// Returns the collision handler that is called for collisions that
+    /// have no more specific handler.
+    ///
+    /// By default this handler calls the wildcard handlers of both shapes.
+    /// Closures set on it replace that behavior unless they call the
+    /// wildcard handlers through the arbiter themselves.
+    pub fn default_collision_handler(&mut self) -> CollisionHandler<T> {
+        unsafe {
+            (*self.raw.get()).default_collision_handler()
+        }
+    }
+
+    /// Records the collisions between shapes of `type_a` and `type_b`.
+    ///
+    /// During `step()`, the start, end and every solved step of these
+    /// collisions are copied into `CollisionEvent`s, which can be collected
+    /// afterwards with `drain_collision_events()`.  Collisions between
+    /// other types are not recorded.
+    pub fn record_collision_events(&mut self, type_a: CollisionType, type_b: CollisionType) {
+        self.add_collision_handler(type_a, type_b).set_record_events(true);
+    }
+
+    /// Records the collisions between shapes of `collision_type` and
+    /// shapes of any type.
+    ///
+    /// This uses the wildcard handler for `collision_type`, so collisions
+    /// that have a pair handler are only recorded if that handler calls
+    /// the wildcard handlers through the arbiter.
+    pub fn record_wildcard_collision_events(&mut self, collision_type: CollisionType) {
+        self.add_wildcard_handler(collision_type).set_record_events(true);
+    }
+
+    /// Tracks the shapes that overlap the shapes of `sensor_type`.
+    ///
+    /// After every step, `sensor_overlaps()` returns the shapes touching a
+    /// sensor, and `sensor_entered()` and `sensor_exited()` list the
+    /// changes made by that step.  Shapes that are removed from the space
+    /// are dropped from every overlap, and if that happens between steps
+    /// the exits are listed after the next step.
+    ///
+    /// This uses the wildcard handler for `sensor_type`, so overlaps that
+    /// have a pair handler are only tracked if that handler calls the
+    /// wildcard handlers through the arbiter.
+    pub fn track_sensors(&mut self, sensor_type: CollisionType) {
+        self.add_wildcard_handler(sensor_type).set_track_sensors(true);
+    }
+
+    /// Turns `platform` into a one-way platform.
+    ///
+    /// Other shapes only collide with the platform when they are on the
+    /// side that `direction` points to, and pass through it from every
+    /// other side.  For a floor that can be jumped through from below, use
+    /// `Vect::new(0.0, 1.0)`.  Every platform keeps its own direction.
+    ///
+    /// The check runs in the pre-solve closure of the wildcard handler for
+    /// the collision type of `platform`, so set that type first.  The
+    /// handler is returned.  A pre-solve closure that was already set on
+    /// it is still called for collisions that aren't ignored, but setting
+    /// one afterwards replaces the check.  Pair handlers for that type only
+    /// run it if they call `Arbiter::call_wildcard_pre_solve()`.
+    pub fn add_one_way_platform<A, P: Into<Vect>>(&mut self, platform: &Shape<A>,
+                                                  direction: P) -> CollisionHandler<T> {
+        unsafe {
+            (*self.raw.get()).add_one_way_platform(platform, direction.into())
+        }
+    }
+}
+
 impl <T: 'static + Any> UserData<T> for SpaceRaw<T> {
     fn get_userdata_box(&self) -> &Option<Box<Any>> {
         &self.user_data
@@ -231,7 +555,16 @@ impl <T> SpaceRaw <T> {
                 cp_space: mem::zeroed(),
                 user_data: None,
                 bodies: Vec::new(),
-                shapes: Vec::new(),
+                shapes: HashMap::new(),
+                constraints: HashMap::new(),
+                broken_constraints: Vec::new(),
+                collision_handlers: Vec::new(),
+                collision_events: Vec::new(),
+                sensor_tracker: SensorTracker::new(),
+                one_way_platforms: Rc::new(RefCell::new(HashMap::new())),
+                one_way_types: Vec::new(),
+                arbiter_user_data: HashMap::new(),
+                separated_arbiters: Vec::new(),
                 _phantom: PhantomData
             };
             chip::cpSpaceInit(&mut spr.cp_space);
@@ -248,39 +581,192 @@ impl <T> SpaceRaw <T> {
 
     fn add_shape<B>(&mut self, shape: &mut Shape<B>) {
         unsafe {
-            self.shapes.push(shape.duplicate());
+            self.shapes.insert(shape.get_cp_shape(), shape.duplicate());
             chip::cpSpaceAddShape(&mut self.cp_space, shape.get_cp_shape_mut());
         }
     }
 
+    fn add_constraint<B>(&mut self, constraint: &mut Constraint<B>) {
+        unsafe {
+            self.constraints.insert(constraint.get_cp_constraint(), constraint.duplicate());
+            chip::cpSpaceAddConstraint(&mut self.cp_space, constraint.get_cp_constraint_mut());
+        }
+    }
+
     fn remove_body<B>(&mut self, mut body: Body<B>) {
         unsafe {
+            chip::cpSpaceRemoveBody(&mut self.cp_space, body.get_cp_body());
             let pos = self.bodies.iter_mut().position(|e| e.get_cp_body() == body.get_cp_body());
             if let Some(pos) = pos {
                 self.bodies.remove(pos);
             }
-            chip::cpSpaceRemoveBody(&mut self.cp_space, body.get_cp_body());
         }
     }
 
     fn remove_shape<B>(&mut self, mut shape: Shape<B>) {
         unsafe {
-            let pos = self.shapes.iter_mut().position(|e| e.get_cp_shape() == shape.get_cp_shape());
-            if let Some(pos) = pos {
-                self.shapes.remove(pos);
-            }
+            // Removing the shape calls the separate callbacks of its
+            // collisions, which still need to find it in `shapes`.
             chip::cpSpaceRemoveShape(&mut self.cp_space, shape.get_cp_shape_mut());
+            self.sensor_tracker.forget(shape.get_cp_shape());
+            self.one_way_platforms.borrow_mut().remove(&shape.get_cp_shape());
+            self.shapes.remove(&shape.get_cp_shape());
+        }
+        self.drop_separated_arbiters();
+    }
+
+    fn drop_separated_arbiters(&mut self) {
+        for cp_arbiter in self.separated_arbiters.drain(..) {
+            self.arbiter_user_data.remove(&cp_arbiter);
+        }
+    }
+
+    fn remove_constraint<B>(&mut self, mut constraint: Constraint<B>) {
+        unsafe {
+            self.constraints.remove(&constraint.get_cp_constraint());
+            chip::cpSpaceRemoveConstraint(&mut self.cp_space, constraint.get_cp_constraint_mut());
+        }
+    }
+
+    fn find_shape(&self, cp_shape: *const chip::cpShape) -> Option<Shape<Void>> {
+        unsafe {
+            self.shapes.get(&cp_shape).map(|s| s.duplicate())
+        }
+    }
+
+    /// Chipmunk's queries take a mutable space even though they don't
+    /// change it.
+    fn get_cp_space_mut(&self) -> *mut chip::cpSpace {
+        &self.cp_space as *const chip::cpSpace as *mut chip::cpSpace
+    }
+
+    //
+    // QUERIES
+    //
+
+    fn point_query_nearest<S>(&self, point: Vect, max_distance: f64,
+                              filter: ShapeFilter) -> Option<PointQueryInfo<S>> {
+        unsafe {
+            let mut info: chip::cpPointQueryInfo = mem::zeroed();
+            let cp_shape = chip::cpSpacePointQueryNearest(self.get_cp_space_mut(),
+                                                          point.into(),
+                                                          max_distance, filter.into(), &mut info);
+            if cp_shape.is_null() {
+                return None;
+            }
+            self.find_shape(cp_shape).map(|shape| PointQueryInfo {
+                shape: mem::transmute(shape),
+                point: info.point.into(),
+                distance: info.distance,
+                gradient: info.gradient.into()
+            })
+        }
+    }
+
+    fn point_query<S>(&self, point: Vect, max_distance: f64,
+                      filter: ShapeFilter) -> vec::IntoIter<PointQueryInfo<S>> {
+        let mut hits: Vec<RawPointHit> = Vec::new();
+        unsafe {
+            chip::cpSpacePointQuery(self.get_cp_space_mut(), point.into(),
+                                    max_distance, filter.into(),
+                                    Some(query::point_query_callback),
+                                    &mut hits as *mut Vec<RawPointHit> as chip::cpDataPointer);
+        }
+
+        let results: Vec<PointQueryInfo<S>> = hits.into_iter().filter_map(|hit| {
+            self.find_shape(hit.cp_shape).map(|shape| PointQueryInfo {
+                shape: unsafe { mem::transmute(shape) },
+                point: hit.point.into(),
+                distance: hit.distance,
+                gradient: hit.gradient.into()
+            })
+        }).collect();
+        results.into_iter()
+    }
+
+    fn segment_query_first<S>(&self, start: Vect, end: Vect, radius: f64,
+                              filter: ShapeFilter) -> Option<SegmentQueryInfo<S>> {
+        unsafe {
+            let mut info: chip::cpSegmentQueryInfo = mem::zeroed();
+            let cp_shape = chip::cpSpaceSegmentQueryFirst(self.get_cp_space_mut(),
+                                                          start.into(),
+                                                          end.into(),
+                                                          radius, filter.into(), &mut info);
+            if cp_shape.is_null() {
+                return None;
+            }
+            self.find_shape(cp_shape).map(|shape| SegmentQueryInfo {
+                shape: mem::transmute(shape),
+                point: info.point.into(),
+                normal: info.normal.into(),
+                alpha: info.alpha
+            })
         }
     }
 
+    fn segment_query<S>(&self, start: Vect, end: Vect, radius: f64,
+                        filter: ShapeFilter) -> vec::IntoIter<SegmentQueryInfo<S>> {
+        let mut hits: Vec<RawSegmentHit> = Vec::new();
+        unsafe {
+            chip::cpSpaceSegmentQuery(self.get_cp_space_mut(),
+                                      start.into(), end.into(),
+                                      radius, filter.into(),
+                                      Some(query::segment_query_callback),
+                                      &mut hits as *mut Vec<RawSegmentHit> as chip::cpDataPointer);
+        }
+
+        let mut results: Vec<SegmentQueryInfo<S>> = hits.into_iter().filter_map(|hit| {
+            self.find_shape(hit.cp_shape).map(|shape| SegmentQueryInfo {
+                shape: unsafe { mem::transmute(shape) },
+                point: hit.point.into(),
+                normal: hit.normal.into(),
+                alpha: hit.alpha
+            })
+        }).collect();
+        results.sort_by(|a, b| a.alpha.partial_cmp(&b.alpha).unwrap_or(Ordering::Equal));
+        results.into_iter()
+    }
+
+    fn bb_query<S>(&self, bb: BB, filter: ShapeFilter) -> vec::IntoIter<Shape<S>> {
+        let mut hits: Vec<*mut chip::cpShape> = Vec::new();
+        unsafe {
+            chip::cpSpaceBBQuery(self.get_cp_space_mut(), bb.into(), filter.into(),
+                                 Some(query::bb_query_callback),
+                                 &mut hits as *mut Vec<*mut chip::cpShape> as chip::cpDataPointer);
+        }
+
+        let results: Vec<Shape<S>> = hits.into_iter().filter_map(|cp_shape| {
+            self.find_shape(cp_shape).map(|shape| unsafe { mem::transmute(shape) })
+        }).collect();
+        results.into_iter()
+    }
+
+    fn shape_query<S>(&self, shape: &Shape<S>) -> vec::IntoIter<ShapeQueryInfo<T>> {
+        let mut hits: Vec<(*mut chip::cpShape, ContactPointSet)> = Vec::new();
+        unsafe {
+            let cp_shape = shape.get_cp_shape() as *mut chip::cpShape;
+            chip::cpSpaceShapeQuery(self.get_cp_space_mut(), cp_shape,
+                                    Some(query::shape_query_callback),
+                                    &mut hits as *mut Vec<(*mut chip::cpShape, ContactPointSet)>
+                                              as chip::cpDataPointer);
+        }
+
+        let results: Vec<ShapeQueryInfo<T>> = hits.into_iter().filter_map(|(cp_shape, points)| {
+            self.find_shape(cp_shape).map(|shape| ShapeQueryInfo {
+                shape: unsafe { mem::transmute(shape) },
+                contact_point_set: points
+            })
+        }).collect();
+        results.into_iter()
+    }
+
     //
     // GETTERS
     //
 
-    fn gravity(&self) -> (f64, f64) {
+    fn gravity(&self) -> Vect {
         unsafe {
-            let vec = chip::cpSpaceGetGravity(&self.cp_space);
-            (vec.x, vec.y)
+            chip::cpSpaceGetGravity(&self.cp_space).into()
         }
     }
 
@@ -330,9 +816,9 @@ impl <T> SpaceRaw <T> {
     // Setters
     //
 
-    fn set_gravity(&mut self, ax: f64, ay: f64) {
+    fn set_gravity(&mut self, gravity: Vect) {
         unsafe {
-            chip::cpSpaceSetGravity(&mut self.cp_space, chip::cpv(ax, ay));
+            chip::cpSpaceSetGravity(&mut self.cp_space, gravity.into());
         }
     }
 
@@ -379,9 +865,110 @@ impl <T> SpaceRaw <T> {
     }
 
     fn step(&mut self, timestep: f64) {
+        self.sensor_tracker.begin_step();
         unsafe {
             chip::cpSpaceStep(&mut self.cp_space, timestep);
         }
+        self.sensor_tracker.end_step();
+        self.drop_separated_arbiters();
+        self.break_constraints(timestep);
+    }
+
+    fn break_constraints(&mut self, timestep: f64) {
+        let broken: Vec<_> = self.constraints.iter()
+                                             .filter(|&(_, c)| c.exceeds_break_threshold(timestep))
+                                             .map(|(&cp_constraint, _)| cp_constraint)
+                                             .collect();
+        for cp_constraint in broken {
+            if let Some(mut constraint) = self.constraints.remove(&cp_constraint) {
+                unsafe {
+                    chip::cpSpaceRemoveConstraint(&mut self.cp_space, constraint.get_cp_constraint_mut());
+                }
+                self.broken_constraints.push(constraint);
+            }
+        }
+    }
+
+    fn drain_broken_constraints(&mut self) -> Vec<Constraint<Void>> {
+        mem::replace(&mut self.broken_constraints, Vec::new())
+    }
+
+    fn sensor_entered(&self) -> Vec<(Shape, Shape)> {
+        self.sensor_tracker.entered()
+    }
+
+    fn sensor_exited(&self) -> Vec<(Shape, Shape)> {
+        self.sensor_tracker.exited()
+    }
+
+    fn drain_collision_events(&mut self) -> Vec<CollisionEvent> {
+        mem::replace(&mut self.collision_events, Vec::new())
+    }
+}
+
+impl <T: 'static + Any> SpaceRaw<T> {
+    fn add_collision_handler(&mut self, type_a: CollisionType,
+                             type_b: CollisionType) -> CollisionHandler<T> {
+        unsafe {
+            let cp_handler = chip::cpSpaceAddCollisionHandler(&mut self.cp_space, type_a, type_b);
+            self.wrap_collision_handler(cp_handler)
+        }
+    }
+
+    fn add_wildcard_handler(&mut self, collision_type: CollisionType) -> CollisionHandler<T> {
+        unsafe {
+            let cp_handler = chip::cpSpaceAddWildcardHandler(&mut self.cp_space, collision_type);
+            self.wrap_collision_handler(cp_handler)
+        }
+    }
+
+    fn default_collision_handler(&mut self) -> CollisionHandler<T> {
+        unsafe {
+            let cp_handler = chip::cpSpaceAddDefaultCollisionHandler(&mut self.cp_space);
+            self.wrap_collision_handler(cp_handler)
+        }
+    }
+
+    fn add_one_way_platform<B>(&mut self, platform: &Shape<B>, direction: Vect) -> CollisionHandler<T> {
+        let collision_type = platform.collision_type();
+        unsafe {
+            self.one_way_platforms.borrow_mut().insert(platform.get_cp_shape(), direction);
+        }
+        let mut handler = self.add_wildcard_handler(collision_type);
+        if self.one_way_types.contains(&collision_type) {
+            return handler;
+        }
+        self.one_way_types.push(collision_type);
+
+        let platforms = self.one_way_platforms.clone();
+        let mut chained = unsafe { handler.take_pre_solve_func() };
+        handler.set_pre_solve_func(move |arbiter, user_data| {
+            // The platform is always the first shape in its wildcard handler.
+            let direction = arbiter.shapes::<Void>().and_then(|(platform, _)| unsafe {
+                platforms.borrow().get(&platform.get_cp_shape()).cloned()
+            });
+            if let Some(direction) = direction {
+                if arbiter.normal().dot(direction) < 0.0 {
+                    return arbiter.ignore();
+                }
+            }
+            match chained {
+                Some(ref mut func) => func(arbiter, user_data),
+                None => true
+            }
+        });
+        handler
+    }
+
+    /// Returns the handler that wraps `cp_handler`, creating it the first
+    /// time that chipmunk hands out the handler.
+    unsafe fn wrap_collision_handler(&mut self, cp_handler: *mut chip::cpCollisionHandler) -> CollisionHandler<T> {
+        if let Some(handler) = self.collision_handlers.iter().find(|h| h.get_cp_handler() == cp_handler) {
+            return handler.duplicate();
+        }
+        let handler = CollisionHandler::from_cp_handler(cp_handler);
+        self.collision_handlers.push(handler.duplicate());
+        handler
     }
 }
 
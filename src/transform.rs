@@ -0,0 +1,142 @@
+use std::ops::Mul;
+
+use super::vect::Vect;
+use super::bb::BB;
+use super::angle::Radians;
+
+use chip;
+
+/// An affine transformation.
+///
+/// Maps a point `(x, y)` to `(a*x + c*y + tx, b*x + d*y + ty)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub tx: f64,
+    pub ty: f64
+}
+
+impl Transform {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, tx: f64, ty: f64) -> Transform {
+        Transform { a: a, b: b, c: c, d: d, tx: tx, ty: ty }
+    }
+
+    /// Returns the transformation that leaves everything in place.
+    pub fn identity() -> Transform {
+        Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Returns a transformation that moves points by `offset`.
+    pub fn translate<P: Into<Vect>>(offset: P) -> Transform {
+        let offset: Vect = offset.into();
+        Transform::new(1.0, 0.0, 0.0, 1.0, offset.x, offset.y)
+    }
+
+    /// Returns a transformation that rotates counter-clockwise around the
+    /// origin by `angle`.
+    pub fn rotate<A: Into<Radians>>(angle: A) -> Transform {
+        let rot = Vect::for_angle(angle);
+        Transform::new(rot.x, rot.y, -rot.y, rot.x, 0.0, 0.0)
+    }
+
+    /// Returns a transformation that scales along both axes.
+    pub fn scale(sx: f64, sy: f64) -> Transform {
+        Transform::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// Returns a transformation that rotates by `angle` and then moves by
+    /// `offset`.
+    pub fn rigid<P: Into<Vect>, A: Into<Radians>>(offset: P, angle: A) -> Transform {
+        Transform::translate(offset).compose(Transform::rotate(angle))
+    }
+
+    /// Returns the transformation that applies `other` first and then
+    /// this one.
+    ///
+    /// The same as `self * other`.
+    pub fn compose(self, other: Transform) -> Transform {
+        Transform::new(self.a * other.a + self.c * other.b,
+                       self.b * other.a + self.d * other.b,
+                       self.a * other.c + self.c * other.d,
+                       self.b * other.c + self.d * other.d,
+                       self.a * other.tx + self.c * other.ty + self.tx,
+                       self.b * other.tx + self.d * other.ty + self.ty)
+    }
+
+    /// Returns the transformation that undoes this one.
+    ///
+    /// The result is not finite if the transformation can't be undone,
+    /// e.g. if it scales by 0.
+    pub fn invert(self) -> Transform {
+        let inv_det = 1.0 / (self.a * self.d - self.c * self.b);
+        Transform::new(self.d * inv_det,
+                       -self.b * inv_det,
+                       -self.c * inv_det,
+                       self.a * inv_det,
+                       (self.c * self.ty - self.tx * self.d) * inv_det,
+                       (self.tx * self.b - self.a * self.ty) * inv_det)
+    }
+
+    /// Applies the transformation to a point.
+    pub fn transform_point<P: Into<Vect>>(&self, p: P) -> Vect {
+        let p: Vect = p.into();
+        Vect::new(self.a * p.x + self.c * p.y + self.tx,
+                  self.b * p.x + self.d * p.y + self.ty)
+    }
+
+    /// Applies the transformation to a direction, ignoring the
+    /// translation.
+    pub fn transform_vect<P: Into<Vect>>(&self, v: P) -> Vect {
+        let v: Vect = v.into();
+        Vect::new(self.a * v.x + self.c * v.y,
+                  self.b * v.x + self.d * v.y)
+    }
+
+    /// Returns a box that encloses `bb` after it has been transformed.
+    pub fn transform_bb(&self, bb: BB) -> BB {
+        let center = self.transform_point(bb.center());
+        let hw = bb.width() * 0.5;
+        let hh = bb.height() * 0.5;
+        let (a, b) = (self.a * hw, self.c * hh);
+        let (d, e) = (self.b * hw, self.d * hh);
+        BB::for_extents(center,
+                        (a + b).abs().max((a - b).abs()),
+                        (d + e).abs().max((d - e).abs()))
+    }
+
+    /// Returns the transformation as a 3x3 matrix in column-major order,
+    /// as expected by most graphics APIs.
+    pub fn to_matrix(&self) -> [[f64; 3]; 3] {
+        [[self.a, self.b, 0.0],
+         [self.c, self.d, 0.0],
+         [self.tx, self.ty, 1.0]]
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+    fn mul(self, other: Transform) -> Transform {
+        self.compose(other)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
+
+impl From<chip::cpTransform> for Transform {
+    fn from(t: chip::cpTransform) -> Transform {
+        Transform::new(t.a, t.b, t.c, t.d, t.tx, t.ty)
+    }
+}
+
+impl From<Transform> for chip::cpTransform {
+    fn from(t: Transform) -> chip::cpTransform {
+        chip::cpTransform { a: t.a, b: t.b, c: t.c, d: t.d, tx: t.tx, ty: t.ty }
+    }
+}
@@ -1,7 +1,106 @@
+use super::vect::Vect;
+use super::bb::BB;
+
 use chip;
 
-pub fn moment_of_circle(mass: f64, r1: f64, r2: f64) -> f64 {
+fn to_cp_verts<P: Into<Vect> + Copy>(verts: &[P]) -> Vec<chip::cpVect> {
+    verts.iter().map(|&v| {
+        let v: Vect = v.into();
+        v.into()
+    }).collect()
+}
+
+/// Returns the moment of inertia of a hollow circle.
+///
+/// `r1` and `r2` are the inner and outer radii; use 0.0 for `r1` to get a
+/// solid circle.  `offset` is the center of the circle relative to the
+/// center of gravity of the body.
+pub fn moment_of_circle<P: Into<Vect>>(mass: f64, r1: f64, r2: f64, offset: P) -> f64 {
+    let offset: Vect = offset.into();
+    unsafe {
+        chip::cpMomentForCircle(mass, r1, r2, offset.into())
+    }
+}
+
+/// Returns the area of a hollow circle.
+pub fn area_of_circle(r1: f64, r2: f64) -> f64 {
+    unsafe {
+        chip::cpAreaForCircle(r1, r2)
+    }
+}
+
+/// Returns the moment of inertia of a segment from `a` to `b`, rounded by
+/// `radius`.
+///
+/// The points are relative to the center of gravity of the body.
+pub fn moment_of_segment<P: Into<Vect>, Q: Into<Vect>>(mass: f64, a: P, b: Q, radius: f64) -> f64 {
+    let (a, b): (Vect, Vect) = (a.into(), b.into());
+    unsafe {
+        chip::cpMomentForSegment(mass, a.into(), b.into(), radius)
+    }
+}
+
+/// Returns the area of a segment from `a` to `b`, rounded by `radius`.
+pub fn area_of_segment<P: Into<Vect>, Q: Into<Vect>>(a: P, b: Q, radius: f64) -> f64 {
+    let (a, b): (Vect, Vect) = (a.into(), b.into());
+    unsafe {
+        chip::cpAreaForSegment(a.into(), b.into(), radius)
+    }
+}
+
+/// Returns the moment of inertia of a convex polygon, rounded by `radius`.
+///
+/// The vertices are moved by `offset` first, so that the polygon can be
+/// placed relative to the center of gravity of the body.
+pub fn moment_of_poly<P: Into<Vect> + Copy, O: Into<Vect>>(mass: f64, verts: &[P], offset: O,
+                                                           radius: f64) -> f64 {
+    let verts = to_cp_verts(verts);
+    let offset: Vect = offset.into();
     unsafe {
-        chip::cpMomentForCircle(mass, r1, r2, chip::cpv(0.0, 0.0))
+        chip::cpMomentForPoly(mass, verts.len() as i32, verts.as_ptr(), offset.into(), radius)
     }
 }
+
+/// Returns the signed area of a convex polygon, rounded by `radius`.
+///
+/// The area is positive if the vertices are in counter-clockwise order.
+pub fn area_of_poly<P: Into<Vect> + Copy>(verts: &[P], radius: f64) -> f64 {
+    let verts = to_cp_verts(verts);
+    unsafe {
+        chip::cpAreaForPoly(verts.len() as i32, verts.as_ptr(), radius)
+    }
+}
+
+/// Returns the centroid of a polygon.
+///
+/// Moving the vertices by the negated centroid puts the center of gravity
+/// of the polygon at the origin.
+pub fn centroid_of_poly<P: Into<Vect> + Copy>(verts: &[P]) -> Vect {
+    let verts = to_cp_verts(verts);
+    unsafe {
+        chip::cpCentroidForPoly(verts.len() as i32, verts.as_ptr()).into()
+    }
+}
+
+/// Returns the moment of inertia of a solid box centered on the center of
+/// gravity of the body.
+pub fn moment_of_box(mass: f64, width: f64, height: f64) -> f64 {
+    unsafe {
+        chip::cpMomentForBox(mass, width, height)
+    }
+}
+
+/// Returns the moment of inertia of a solid box, relative to the center of
+/// gravity of the body.
+pub fn moment_of_box_bb(mass: f64, bb: BB) -> f64 {
+    unsafe {
+        chip::cpMomentForBox2(mass, bb.into())
+    }
+}
+
+/// Returns the area of a box, rounded by `radius`.
+pub fn area_of_box(width: f64, height: f64, radius: f64) -> f64 {
+    let (hw, hh) = (width / 2.0, height / 2.0);
+    area_of_poly(&[Vect::new(hw, -hh), Vect::new(hw, hh),
+                   Vect::new(-hw, hh), Vect::new(-hw, -hh)], radius)
+}
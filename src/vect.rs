@@ -0,0 +1,236 @@
+use std::ops::{Add, Sub, Neg, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
+
+use super::angle::Radians;
+
+use chip;
+
+/// A two dimensional vector.
+///
+/// Used for positions, velocities, forces and every other point or
+/// direction in the library.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct Vect {
+    pub x: f64,
+    pub y: f64
+}
+
+impl Vect {
+    pub fn new(x: f64, y: f64) -> Vect {
+        Vect { x: x, y: y }
+    }
+
+    /// Returns the zero vector.
+    pub fn zero() -> Vect {
+        Vect::new(0.0, 0.0)
+    }
+
+    /// Returns the unit vector pointing at `angle`.
+    pub fn for_angle<A: Into<Radians>>(angle: A) -> Vect {
+        let Radians(angle) = angle.into();
+        Vect::new(angle.cos(), angle.sin())
+    }
+
+    /// Returns the angle that this vector points at.
+    pub fn to_angle(self) -> Radians {
+        Radians(self.y.atan2(self.x))
+    }
+
+    pub fn dot(self, other: Vect) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z component of the 3D cross product of both vectors.
+    pub fn cross(self, other: Vect) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns this vector rotated 90 degrees counter-clockwise.
+    pub fn perp(self) -> Vect {
+        Vect::new(-self.y, self.x)
+    }
+
+    /// Returns this vector rotated 90 degrees clockwise.
+    pub fn rperp(self) -> Vect {
+        Vect::new(self.y, -self.x)
+    }
+
+    /// Returns this vector rotated counter-clockwise by `angle`.
+    pub fn rotate<A: Into<Radians>>(self, angle: A) -> Vect {
+        self.rotate_by(Vect::for_angle(angle))
+    }
+
+    /// Returns this vector rotated by the angle of `other`, scaled by the
+    /// length of `other`.
+    ///
+    /// This is complex multiplication.
+    pub fn rotate_by(self, other: Vect) -> Vect {
+        Vect::new(self.x * other.x - self.y * other.y,
+                  self.x * other.y + self.y * other.x)
+    }
+
+    /// The inverse of `rotate_by()`.
+    pub fn unrotate_by(self, other: Vect) -> Vect {
+        Vect::new(self.x * other.x + self.y * other.y,
+                  self.y * other.x - self.x * other.y)
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the squared length, which is faster to compute than
+    /// `length()`.
+    pub fn length_sq(self) -> f64 {
+        self.dot(self)
+    }
+
+    pub fn dist(self, other: Vect) -> f64 {
+        (self - other).length()
+    }
+
+    /// Returns a unit vector in the direction of this vector.
+    ///
+    /// The zero vector is returned unchanged.
+    pub fn normalize(self) -> Vect {
+        use std::f64::MIN_POSITIVE;
+        self * (1.0 / (self.length() + MIN_POSITIVE))
+    }
+
+    /// Returns this vector with its length clamped to `len`.
+    pub fn clamp(self, len: f64) -> Vect {
+        if self.length_sq() > len * len {
+            self.normalize() * len
+        } else {
+            self
+        }
+    }
+
+    /// Linearly interpolates between this vector and `other`.
+    pub fn lerp(self, other: Vect, t: f64) -> Vect {
+        self * (1.0 - t) + other * t
+    }
+
+    /// Spherically interpolates between this vector and `other`.
+    ///
+    /// Both vectors should be unit length.
+    pub fn slerp(self, other: Vect, t: f64) -> Vect {
+        let dot = self.normalize().dot(other.normalize());
+        let omega = dot.max(-1.0).min(1.0).acos();
+
+        if omega < 1e-3 {
+            // Too close together to divide by sin(omega).
+            self.lerp(other, t)
+        } else {
+            let denom = 1.0 / omega.sin();
+            self * (((1.0 - t) * omega).sin() * denom) + other * ((t * omega).sin() * denom)
+        }
+    }
+
+    /// Returns this vector projected onto `other`.
+    pub fn project(self, other: Vect) -> Vect {
+        other * (self.dot(other) / other.dot(other))
+    }
+}
+
+impl Add for Vect {
+    type Output = Vect;
+    fn add(self, other: Vect) -> Vect {
+        Vect::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vect {
+    type Output = Vect;
+    fn sub(self, other: Vect) -> Vect {
+        Vect::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vect {
+    type Output = Vect;
+    fn neg(self) -> Vect {
+        Vect::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f64> for Vect {
+    type Output = Vect;
+    fn mul(self, scale: f64) -> Vect {
+        Vect::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Mul<Vect> for f64 {
+    type Output = Vect;
+    fn mul(self, v: Vect) -> Vect {
+        v * self
+    }
+}
+
+impl Div<f64> for Vect {
+    type Output = Vect;
+    fn div(self, scale: f64) -> Vect {
+        Vect::new(self.x / scale, self.y / scale)
+    }
+}
+
+impl AddAssign for Vect {
+    fn add_assign(&mut self, other: Vect) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vect {
+    fn sub_assign(&mut self, other: Vect) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f64> for Vect {
+    fn mul_assign(&mut self, scale: f64) {
+        *self = *self * scale;
+    }
+}
+
+impl DivAssign<f64> for Vect {
+    fn div_assign(&mut self, scale: f64) {
+        *self = *self / scale;
+    }
+}
+
+impl From<(f64, f64)> for Vect {
+    fn from(v: (f64, f64)) -> Vect {
+        Vect::new(v.0, v.1)
+    }
+}
+
+impl From<[f64; 2]> for Vect {
+    fn from(v: [f64; 2]) -> Vect {
+        Vect::new(v[0], v[1])
+    }
+}
+
+impl From<Vect> for (f64, f64) {
+    fn from(v: Vect) -> (f64, f64) {
+        (v.x, v.y)
+    }
+}
+
+impl From<Vect> for [f64; 2] {
+    fn from(v: Vect) -> [f64; 2] {
+        [v.x, v.y]
+    }
+}
+
+impl From<chip::cpVect> for Vect {
+    fn from(v: chip::cpVect) -> Vect {
+        Vect::new(v.x, v.y)
+    }
+}
+
+impl From<Vect> for chip::cpVect {
+    fn from(v: Vect) -> chip::cpVect {
+        chip::cpv(v.x, v.y)
+    }
+}
@@ -0,0 +1,198 @@
+extern crate chipmunk;
+extern crate chipmunk_sys as chip;
+
+use std::f64::consts::PI;
+
+use chipmunk::angle::{Radians, Degrees};
+use chipmunk::body::Body;
+use chipmunk::constraint::Constraint;
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+fn bodies() -> (Body, Body) {
+    (Body::new(1.0, 1.0), Body::new(1.0, 1.0))
+}
+
+#[test]
+fn conversions_round_trip() {
+    assert_close(Radians::from(Degrees(180.0)).0, PI);
+    assert_close(Degrees::from(Radians(PI / 2.0)).0, 90.0);
+
+    for &deg in &[-720.0, -45.0, 0.0, 1.0, 33.3, 359.0] {
+        assert_close(Degrees(deg).to_radians().to_degrees().0, deg);
+    }
+}
+
+#[test]
+fn body_angle() {
+    let mut body = Body::new(1.0, 1.0);
+
+    body.set_angle(Radians(1.5));
+    assert_close(unsafe { chip::cpBodyGetAngle(body.get_cp_body()) }, 1.5);
+    assert_close(body.angle().0, 1.5);
+
+    body.set_angle(Degrees(90.0));
+    assert_close(unsafe { chip::cpBodyGetAngle(body.get_cp_body()) }, PI / 2.0);
+    assert_close(body.angle().to_degrees().0, 90.0);
+
+    unsafe { chip::cpBodySetAngle(body.get_cp_body(), PI) };
+    assert_close(body.angle().0, PI);
+    assert_close(body.angle().to_degrees().0, 180.0);
+}
+
+#[test]
+fn body_angular_velocity() {
+    let mut body = Body::new(1.0, 1.0);
+
+    body.set_angular_velocity(Radians(-2.0));
+    assert_close(unsafe { chip::cpBodyGetAngularVelocity(body.get_cp_body()) }, -2.0);
+    assert_close(body.angular_velocity().0, -2.0);
+
+    body.set_angular_velocity(Degrees(360.0));
+    assert_close(unsafe { chip::cpBodyGetAngularVelocity(body.get_cp_body()) }, 2.0 * PI);
+    assert_close(body.angular_velocity().to_degrees().0, 360.0);
+
+    unsafe { chip::cpBodySetAngularVelocity(body.get_cp_body(), PI / 4.0) };
+    assert_close(body.angular_velocity().to_degrees().0, 45.0);
+}
+
+#[test]
+fn damped_rotary_spring_rest_angle() {
+    let (mut a, mut b) = bodies();
+    let mut constraint = Constraint::new_damped_rotary_spring(&mut a, &mut b, Degrees(45.0), 1.0, 1.0);
+    let raw = unsafe { constraint.get_cp_constraint_mut() };
+    assert_close(unsafe { chip::cpDampedRotarySpringGetRestAngle(raw) }, PI / 4.0);
+
+    match constraint {
+        Constraint::RotarySpring(ref mut spring) => {
+            assert_close(spring.rest_angle().to_degrees().0, 45.0);
+
+            spring.set_rest_angle(Radians(0.25));
+            assert_close(unsafe { chip::cpDampedRotarySpringGetRestAngle(raw) }, 0.25);
+            assert_close(spring.rest_angle().0, 0.25);
+
+            spring.set_rest_angle(Degrees(-30.0));
+            assert_close(unsafe { chip::cpDampedRotarySpringGetRestAngle(raw) }, -PI / 6.0);
+
+            unsafe { chip::cpDampedRotarySpringSetRestAngle(raw, PI) };
+            assert_close(spring.rest_angle().to_degrees().0, 180.0);
+        }
+        _ => panic!("expected a damped rotary spring")
+    }
+}
+
+#[test]
+fn simple_motor_rate() {
+    let (mut a, mut b) = bodies();
+    let mut constraint = Constraint::new_simple_motor(&mut a, &mut b, Degrees(180.0));
+    let raw = unsafe { constraint.get_cp_constraint_mut() };
+    assert_close(unsafe { chip::cpSimpleMotorGetRate(raw) }, PI);
+
+    match constraint {
+        Constraint::Motor(ref mut motor) => {
+            assert_close(motor.rate().to_degrees().0, 180.0);
+
+            motor.set_rate(Radians(3.0));
+            assert_close(unsafe { chip::cpSimpleMotorGetRate(raw) }, 3.0);
+            assert_close(motor.rate().0, 3.0);
+
+            motor.set_rate(Degrees(90.0));
+            assert_close(unsafe { chip::cpSimpleMotorGetRate(raw) }, PI / 2.0);
+
+            unsafe { chip::cpSimpleMotorSetRate(raw, -PI) };
+            assert_close(motor.rate().to_degrees().0, -180.0);
+        }
+        _ => panic!("expected a simple motor")
+    }
+}
+
+#[test]
+fn gear_joint_phase() {
+    let (mut a, mut b) = bodies();
+    let mut constraint = Constraint::new_gear_joint(&mut a, &mut b, Degrees(90.0), 2.0);
+    let raw = unsafe { constraint.get_cp_constraint_mut() };
+    assert_close(unsafe { chip::cpGearJointGetPhase(raw) }, PI / 2.0);
+
+    match constraint {
+        Constraint::Gear(ref mut gear) => {
+            assert_close(gear.phase().to_degrees().0, 90.0);
+
+            gear.set_phase(Radians(0.5));
+            assert_close(unsafe { chip::cpGearJointGetPhase(raw) }, 0.5);
+            assert_close(gear.phase().0, 0.5);
+
+            gear.set_phase(Degrees(60.0));
+            assert_close(unsafe { chip::cpGearJointGetPhase(raw) }, PI / 3.0);
+
+            unsafe { chip::cpGearJointSetPhase(raw, PI / 6.0) };
+            assert_close(gear.phase().to_degrees().0, 30.0);
+        }
+        _ => panic!("expected a gear joint")
+    }
+}
+
+#[test]
+fn ratchet_joint_angles() {
+    let (mut a, mut b) = bodies();
+    let mut constraint = Constraint::new_ratchet_joint(&mut a, &mut b, Degrees(10.0), Degrees(30.0));
+    let raw = unsafe { constraint.get_cp_constraint_mut() };
+    assert_close(unsafe { chip::cpRatchetJointGetPhase(raw) }, PI / 18.0);
+    assert_close(unsafe { chip::cpRatchetJointGetRatchet(raw) }, PI / 6.0);
+
+    match constraint {
+        Constraint::Ratchet(ref mut ratchet) => {
+            assert_close(ratchet.phase().to_degrees().0, 10.0);
+            assert_close(ratchet.ratchet().to_degrees().0, 30.0);
+
+            ratchet.set_angle(Degrees(45.0));
+            assert_close(unsafe { chip::cpRatchetJointGetAngle(raw) }, PI / 4.0);
+            ratchet.set_angle(Radians(1.0));
+            assert_close(unsafe { chip::cpRatchetJointGetAngle(raw) }, 1.0);
+            unsafe { chip::cpRatchetJointSetAngle(raw, PI) };
+            assert_close(ratchet.angle().to_degrees().0, 180.0);
+
+            ratchet.set_phase(Degrees(90.0));
+            assert_close(unsafe { chip::cpRatchetJointGetPhase(raw) }, PI / 2.0);
+            ratchet.set_phase(Radians(0.1));
+            assert_close(ratchet.phase().0, 0.1);
+
+            ratchet.set_ratchet(Degrees(15.0));
+            assert_close(unsafe { chip::cpRatchetJointGetRatchet(raw) }, PI / 12.0);
+            ratchet.set_ratchet(Radians(0.2));
+            assert_close(ratchet.ratchet().0, 0.2);
+        }
+        _ => panic!("expected a ratchet joint")
+    }
+}
+
+#[test]
+fn rotary_limit_joint_bounds() {
+    let (mut a, mut b) = bodies();
+    let mut constraint = Constraint::new_rotary_limit_joint(&mut a, &mut b, Degrees(-90.0), Degrees(90.0));
+    let raw = unsafe { constraint.get_cp_constraint_mut() };
+    assert_close(unsafe { chip::cpRotaryLimitJointGetMin(raw) }, -PI / 2.0);
+    assert_close(unsafe { chip::cpRotaryLimitJointGetMax(raw) }, PI / 2.0);
+
+    match constraint {
+        Constraint::RotaryLimit(ref mut limit) => {
+            assert_close(limit.min().to_degrees().0, -90.0);
+            assert_close(limit.max().to_degrees().0, 90.0);
+
+            limit.set_min(Radians(-1.0));
+            assert_close(unsafe { chip::cpRotaryLimitJointGetMin(raw) }, -1.0);
+            limit.set_min(Degrees(-45.0));
+            assert_close(limit.min().0, -PI / 4.0);
+
+            limit.set_max(Radians(1.0));
+            assert_close(unsafe { chip::cpRotaryLimitJointGetMax(raw) }, 1.0);
+            limit.set_max(Degrees(45.0));
+            assert_close(limit.max().0, PI / 4.0);
+
+            unsafe { chip::cpRotaryLimitJointSetMax(raw, PI) };
+            assert_close(limit.max().to_degrees().0, 180.0);
+        }
+        _ => panic!("expected a rotary limit joint")
+    }
+}
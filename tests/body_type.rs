@@ -0,0 +1,63 @@
+extern crate chipmunk;
+extern crate chipmunk_sys as chip;
+
+use std::rc::Rc;
+use std::cell::Cell;
+
+use chipmunk::space::Space;
+use chipmunk::body::{Body, BodyType};
+use chipmunk::constraint::Constraint;
+use chipmunk::vect::Vect;
+
+fn cp_body_type(body: &mut Body) -> BodyType {
+    unsafe { chip::cpBodyGetType(body.get_cp_body()).into() }
+}
+
+#[test]
+fn body_type_changes_outside_a_step() {
+    let mut space = Space::new();
+    let mut body = Body::new(1.0, 1.0);
+    space.add_body(&mut body);
+
+    body.set_body_type(BodyType::Kinematic);
+    assert_eq!(body.body_type(), BodyType::Kinematic);
+    assert_eq!(cp_body_type(&mut body), BodyType::Kinematic);
+
+    space.step(1.0 / 60.0);
+    body.set_body_type(BodyType::Dynamic);
+    assert_eq!(body.body_type(), BodyType::Dynamic);
+    assert_eq!(cp_body_type(&mut body), BodyType::Dynamic);
+}
+
+#[test]
+fn body_type_changes_during_a_step_keep_the_last_one() {
+    let mut space = Space::new();
+    let mut a = Body::new(1.0, 1.0);
+    let mut b = Body::new(1.0, 1.0);
+    b.set_position(Vect::new(10.0, 0.0));
+    space.add_body(&mut a);
+    space.add_body(&mut b);
+
+    // The pre-solve closure runs during the step, so the change has to
+    // wait until the step is over.
+    let seen = Rc::new(Cell::new(None));
+    let changed = Rc::new(Cell::new(false));
+    let mut joint = Constraint::new_pin_joint(&mut a, &mut b, Vect::zero(), Vect::zero());
+    {
+        let (seen, changed) = (seen.clone(), changed.clone());
+        joint.set_pre_solve_func(move |_, a, _| {
+            if !changed.get() {
+                changed.set(true);
+                a.set_body_type(BodyType::Kinematic);
+                a.set_body_type(BodyType::Static);
+                seen.set(Some(a.body_type()));
+            }
+        });
+    }
+    space.add_constraint(&mut joint);
+
+    space.step(1.0 / 60.0);
+    assert_eq!(seen.get(), Some(BodyType::Static));
+    assert_eq!(a.body_type(), BodyType::Static);
+    assert_eq!(cp_body_type(&mut a), BodyType::Static);
+}
@@ -0,0 +1,81 @@
+extern crate chipmunk;
+
+use std::rc::Rc;
+use std::cell::Cell;
+
+use chipmunk::space::Space;
+use chipmunk::body::Body;
+use chipmunk::constraint::Constraint;
+use chipmunk::vect::Vect;
+use chipmunk::angle::Radians;
+
+fn pin_joint(space: &mut Space) -> Constraint {
+    let mut a = Body::new(1.0, 1.0);
+    let mut b = Body::new(1.0, 1.0);
+    b.set_position(Vect::new(10.0, 0.0));
+    space.add_body(&mut a);
+    space.add_body(&mut b);
+    Constraint::new_pin_joint(&mut a, &mut b, Vect::zero(), Vect::zero())
+}
+
+#[test]
+fn spring_force_and_torque_closures_are_called() {
+    let mut space = Space::new();
+    let mut a = Body::new(1.0, 1.0);
+    let mut b = Body::new(1.0, 1.0);
+    b.set_position(Vect::new(10.0, 0.0));
+    space.add_body(&mut a);
+    space.add_body(&mut b);
+
+    let forces = Rc::new(Cell::new(0));
+    let torques = Rc::new(Cell::new(0));
+
+    let mut spring = Constraint::new_damped_spring(&mut a, &mut b, Vect::zero(), Vect::zero(),
+                                                   5.0, 1.0, 0.0);
+    match spring {
+        Constraint::Spring(ref mut s) => {
+            let count = forces.clone();
+            s.set_spring_force_func(move |_| { count.set(count.get() + 1); 0.0 });
+        }
+        _ => panic!("expected a damped spring")
+    }
+
+    let mut rotary = Constraint::new_damped_rotary_spring(&mut a, &mut b, Radians(0.0), 1.0, 0.0);
+    match rotary {
+        Constraint::RotarySpring(ref mut s) => {
+            let count = torques.clone();
+            s.set_spring_torque_func(move |_| { count.set(count.get() + 1); 0.0 });
+        }
+        _ => panic!("expected a damped rotary spring")
+    }
+
+    space.add_constraint(&mut spring);
+    space.add_constraint(&mut rotary);
+    space.step(1.0 / 60.0);
+
+    assert_eq!(forces.get(), 1);
+    assert_eq!(torques.get(), 1);
+}
+
+#[test]
+fn pre_and_post_solve_closures_are_called() {
+    let mut space = Space::new();
+    let mut joint = pin_joint(&mut space);
+
+    let pre = Rc::new(Cell::new(0));
+    let post = Rc::new(Cell::new(0));
+    let (pre_count, post_count) = (pre.clone(), post.clone());
+    joint.set_pre_solve_func(move |_, _, _| pre_count.set(pre_count.get() + 1));
+    joint.set_post_solve_func(move |_, _, _| post_count.set(post_count.get() + 1));
+    space.add_constraint(&mut joint);
+
+    space.step(1.0 / 60.0);
+    space.step(1.0 / 60.0);
+    assert_eq!(pre.get(), 2);
+    assert_eq!(post.get(), 2);
+
+    joint.clear_pre_solve_func();
+    space.step(1.0 / 60.0);
+    assert_eq!(pre.get(), 2);
+    assert_eq!(post.get(), 3);
+}
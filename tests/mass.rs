@@ -0,0 +1,46 @@
+extern crate chipmunk;
+
+use std::f64::consts::PI;
+
+use chipmunk::space::Space;
+use chipmunk::body::Body;
+use chipmunk::shape::Shape;
+use chipmunk::vect::Vect;
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+fn assert_vect_close(a: Vect, b: Vect) {
+    assert_close(a.x, b.x);
+    assert_close(a.y, b.y);
+}
+
+#[test]
+fn dynamic_body_mass_follows_its_shapes() {
+    let mut space = Space::new();
+    let mut body = Body::new_dynamic();
+    space.add_body(&mut body);
+
+    // A unit circle with a density of 1 has a mass of pi and a moment of
+    // pi / 2 around its center.
+    let mut center = Shape::new_circle(&mut body, 1.0, Vect::zero());
+    center.set_density(1.0);
+    space.add_shape(&mut center);
+    assert_close(body.mass(), PI);
+    assert_close(body.moment(), PI / 2.0);
+    assert_vect_close(body.center_of_gravity(), Vect::zero());
+
+    // Both circles are 2.0 away from the combined center of gravity.
+    let mut side = Shape::new_circle(&mut body, 1.0, Vect::new(4.0, 0.0));
+    side.set_density(1.0);
+    space.add_shape(&mut side);
+    assert_close(body.mass(), 2.0 * PI);
+    assert_close(body.moment(), 2.0 * (PI / 2.0 + PI * 4.0));
+    assert_vect_close(body.center_of_gravity(), Vect::new(2.0, 0.0));
+
+    space.remove_shape(center);
+    assert_close(body.mass(), PI);
+    assert_close(body.moment(), PI / 2.0);
+    assert_vect_close(body.center_of_gravity(), Vect::new(4.0, 0.0));
+}
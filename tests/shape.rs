@@ -0,0 +1,81 @@
+extern crate chipmunk;
+
+use chipmunk::body::Body;
+use chipmunk::shape::{self, Shape};
+use chipmunk::vect::Vect;
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+fn assert_vect_close(a: Vect, b: Vect) {
+    assert_close(a.x, b.x);
+    assert_close(a.y, b.y);
+}
+
+#[test]
+fn point_query_on_a_circle() {
+    let mut body = Body::new(1.0, 1.0);
+    let mut circle = Shape::new_circle(&mut body, 1.0, Vect::zero());
+
+    let info = circle.point_query(Vect::new(3.0, 0.0));
+    assert_close(info.distance, 2.0);
+    assert_vect_close(info.point, Vect::new(1.0, 0.0));
+    assert!(info.gradient.x > 0.0);
+
+    // Points inside the shape have a negative distance.
+    let info = circle.point_query(Vect::new(0.5, 0.0));
+    assert_close(info.distance, -0.5);
+    assert!(info.gradient.x > 0.0);
+}
+
+#[test]
+fn point_query_on_a_segment() {
+    let mut body = Body::new(1.0, 1.0);
+    let mut segment = Shape::new_segment(&mut body, Vect::new(-5.0, 0.0), Vect::new(5.0, 0.0), 0.0);
+
+    let info = segment.point_query(Vect::new(0.0, 2.0));
+    assert_close(info.distance, 2.0);
+    assert_vect_close(info.point, Vect::zero());
+    assert!(info.gradient.y > 0.0);
+}
+
+#[test]
+fn segment_query_on_a_circle() {
+    let mut body = Body::new(1.0, 1.0);
+    let mut circle = Shape::new_circle(&mut body, 1.0, Vect::zero());
+
+    let hit = circle.segment_query(Vect::new(-3.0, 0.0), Vect::new(3.0, 0.0), 0.0).unwrap();
+    assert_vect_close(hit.point, Vect::new(-1.0, 0.0));
+    assert_vect_close(hit.normal, Vect::new(-1.0, 0.0));
+    assert_close(hit.alpha, 1.0 / 3.0);
+
+    assert!(circle.segment_query(Vect::new(-3.0, 2.0), Vect::new(3.0, 2.0), 0.0).is_none());
+}
+
+#[test]
+fn segment_query_on_a_segment() {
+    let mut body = Body::new(1.0, 1.0);
+    let mut segment = Shape::new_segment(&mut body, Vect::new(-5.0, 0.0), Vect::new(5.0, 0.0), 0.5);
+
+    let hit = segment.segment_query(Vect::new(0.0, -3.0), Vect::new(0.0, 3.0), 0.0).unwrap();
+    assert_vect_close(hit.point, Vect::new(0.0, -0.5));
+    assert_vect_close(hit.normal, Vect::new(0.0, -1.0));
+    assert_close(hit.alpha, 2.5 / 6.0);
+
+    assert!(segment.segment_query(Vect::new(6.0, -3.0), Vect::new(6.0, 3.0), 0.0).is_none());
+}
+
+#[test]
+fn shapes_collide_counts_contacts() {
+    let mut a_body = Body::new(1.0, 1.0);
+    let mut b_body = Body::new(1.0, 1.0);
+    let mut a = Shape::new_circle(&mut a_body, 1.0, Vect::zero());
+    let mut b = Shape::new_circle(&mut b_body, 1.0, Vect::zero());
+
+    b_body.set_position(Vect::new(1.5, 0.0));
+    assert_eq!(shape::shapes_collide(&mut a, &mut b).count, 1);
+
+    b_body.set_position(Vect::new(5.0, 0.0));
+    assert_eq!(shape::shapes_collide(&mut a, &mut b).count, 0);
+}
@@ -0,0 +1,26 @@
+extern crate chipmunk;
+extern crate chipmunk_sys as chip;
+
+use chipmunk::angle::Radians;
+use chipmunk::body::Body;
+use chipmunk::vect::Vect;
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn body_transform_matches_local_to_world() {
+    let mut body = Body::new(1.0, 1.0);
+    body.set_center_of_gravity(Vect::new(1.0, 2.0));
+    body.set_position(Vect::new(3.0, -1.0));
+    body.set_angle(Radians(0.7));
+
+    let transform = body.transform();
+    for &point in &[Vect::zero(), Vect::new(1.0, 2.0), Vect::new(-4.0, 0.5)] {
+        let expected: Vect = unsafe { chip::cpBodyLocalToWorld(body.get_cp_body(), point.into()).into() };
+        let actual = transform.transform_point(point);
+        assert_close(actual.x, expected.x);
+        assert_close(actual.y, expected.y);
+    }
+}